@@ -0,0 +1,183 @@
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::{CalibrationDocument, TokenMatch, Vocabulary};
+
+/**
+ * How the calibration value of one line was found. The token ranges are byte offsets into
+ * `line`, which has its surrounding whitespace trimmed
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LineExplanation<'a> {
+    pub line_number: usize,
+    pub line: &'a str,
+    pub first: Option<TokenMatch<'a>>,
+    pub last: Option<TokenMatch<'a>>,
+    /**
+     * `None` if the line has no token
+     */
+    pub value: Option<usize>,
+    /**
+     * The sum of the values of this line and all before it
+     */
+    pub total: usize,
+}
+
+/**
+ * A line that two vocabularies read differently, like the ones of part 1 and part 2
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Disagreement<'a> {
+    pub line_number: usize,
+    pub line: &'a str,
+    pub before: LineExplanation<'a>,
+    pub after: LineExplanation<'a>,
+}
+
+impl CalibrationDocument {
+    /**
+     * Every line with the tokens `vocabulary` finds in it, its value and the running total
+     */
+    pub fn explain(&self, vocabulary: &Vocabulary) -> Vec<LineExplanation<'_>> {
+        let mut total = 0;
+        self.lines
+            .iter()
+            .map(|(line_number, line)| {
+                let first = vocabulary.first(line);
+                let last = vocabulary.last(line);
+                let value = first
+                    .as_ref()
+                    .zip(last.as_ref())
+                    .map(|(first, last)| usize::from(first.value) * 10 + usize::from(last.value));
+                total += value.unwrap_or(0);
+                LineExplanation {
+                    line_number: *line_number,
+                    line,
+                    first,
+                    last,
+                    value,
+                    total,
+                }
+            })
+            .collect()
+    }
+
+    /**
+     * The lines whose value is different when read with `after` instead of `before`
+     */
+    pub fn diff(&self, before: &Vocabulary, after: &Vocabulary) -> Vec<Disagreement<'_>> {
+        self.explain(before)
+            .into_iter()
+            .zip(self.explain(after))
+            .filter(|(before, after)| before.value != after.value)
+            .map(|(before, after)| Disagreement {
+                line_number: before.line_number,
+                line: before.line,
+                before,
+                after,
+            })
+            .collect()
+    }
+}
+
+impl Display for TokenMatch<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "\"{}\" at {}..{}",
+            self.token, self.range.start, self.range.end
+        )
+    }
+}
+
+impl LineExplanation<'_> {
+    /**
+     * What was found, without the line itself
+     */
+    fn findings(&self) -> String {
+        match (&self.first, &self.last, self.value) {
+            (Some(first), Some(last), Some(value)) => {
+                format!("{first} and {last} make {value}, total {}", self.total)
+            }
+            _ => format!("no number, total {}", self.total),
+        }
+    }
+}
+
+impl Display for LineExplanation<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>5}: {}\n       {}",
+            self.line_number,
+            self.line,
+            self.findings()
+        )
+    }
+}
+
+impl Display for Disagreement<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>5}: {}\n       before: {}\n       after:  {}",
+            self.line_number,
+            self.line,
+            self.before.findings(),
+            self.after.findings()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Solver;
+
+    use super::*;
+
+    const SPELLED_OUT: &str = include_str!("../../examples/spelled_out.txt");
+
+    #[test]
+    fn every_line_is_explained_with_a_running_total() {
+        let document = CalibrationDocument::parse("a1b2c3\nnone\n\nx7\n").unwrap();
+        let explanations = document.explain(&Vocabulary::digits());
+        assert_eq!(
+            explanations
+                .iter()
+                .map(|line| (line.line_number, line.value, line.total))
+                .collect::<Vec<_>>(),
+            vec![(1, Some(13), 13), (2, None, 13), (4, Some(77), 90)]
+        );
+        assert_eq!(explanations[0].last.as_ref().unwrap().range, 5..6);
+        assert_eq!(
+            explanations[0].to_string(),
+            "    1: a1b2c3\n       \"1\" at 1..2 and \"3\" at 5..6 make 13, total 13"
+        );
+        assert_eq!(
+            explanations[1].to_string(),
+            "    2: none\n       no number, total 13"
+        );
+    }
+
+    #[test]
+    fn only_disagreeing_lines_are_diffed() {
+        let document = CalibrationDocument::parse(SPELLED_OUT).unwrap();
+        let diff = document.diff(&Vocabulary::digits(), &Vocabulary::english());
+        assert_eq!(
+            diff.iter()
+                .map(|disagreement| disagreement.line_number)
+                .collect::<Vec<_>>(),
+            // "4nineeightseven2" is 42 either way
+            vec![1, 2, 3, 4, 6, 7]
+        );
+        assert_eq!(
+            diff[0].to_string(),
+            "    1: two1nine\n       before: \"1\" at 3..4 and \"1\" at 3..4 make 11, total 11\n       after:  \"two\" at 0..3 and \"nine\" at 4..8 make 29, total 29"
+        );
+        let json = serde_json::to_value(&diff[2]).unwrap();
+        assert_eq!(json["after"]["first"]["token"], "one");
+        assert_eq!(json["after"]["first"]["range"]["start"], 3);
+        assert_eq!(json["before"]["value"], 22);
+    }
+}
@@ -1,52 +1,103 @@
-use once_cell::sync::Lazy;
-use regex::Regex;
-
-fn line_sum(line: &str) -> usize {
-    static NUMBER_MATCHER: Lazy<Regex> = Lazy::new(|| Regex::new(r"([0-9])").unwrap());
-    let mut matches = NUMBER_MATCHER.captures_iter(line);
-    let first = matches
-        .nth(0)
-        .expect("Could not find a number in this line")
-        .extract::<1>()
-        .1[0];
-    let last = matches.last();
-
-    match last {
-        Some(last_number) => first.to_owned() + last_number.extract::<1>().1[0],
-        _ => first.to_owned() + first,
-    }
-    .parse::<usize>()
-    .expect("Could not parse number. This should not happen")
-}
+use aoc_core::{parse_lines, Answer, Day, NoSolution, ParseError, Registry, Solver};
 
-pub fn multi_line_sum(input: &str) -> usize {
+pub mod explain;
+pub mod vocabulary;
+
+pub use explain::{Disagreement, LineExplanation};
+pub use vocabulary::{TokenMatch, Vocabulary};
+
+/**
+ * Panics if a line has no token of `vocabulary`
+ */
+pub fn multi_line_sum_with(input: &str, vocabulary: &Vocabulary) -> usize {
     input
         .split("\n")
-        .filter(|line| !line.trim().eq(""))
-        .map(|line| line_sum(line))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            vocabulary
+                .calibration_value(line)
+                .expect("Could not find a number in this line")
+        })
         .sum()
 }
 
-fn is_literal_number(char: &str) -> bool {
-    static NUMBER_MATCHER: Lazy<Regex> = Lazy::new(|| Regex::new(r"([0-9])").unwrap());
-    NUMBER_MATCHER.is_match(char)
+pub fn multi_line_sum(input: &str) -> usize {
+    multi_line_sum_with(input, &Vocabulary::digits())
 }
 
-fn is_spelled_out_number(candidate: &str) -> bool {
-    todo!()
+pub fn multi_line_sum_with_spelled_out(input: &str) -> usize {
+    multi_line_sum_with(input, &Vocabulary::english())
 }
 
-fn get_first_number(line: &str) -> usize {
-    let temp = "".to_owned();
-    for char in line.split("").into_iter() {
-        if is_literal_number(char) {
-            return char
-                .parse::<usize>()
-                .expect("Could not parse string that was matched as number. What's going on?");
-        } else {
-            if (is_spelled_out_number(&temp)) {
-                return todo!();
-            }
-        }
-    };
+/**
+ * The calibration document, with the line number of every non-empty line
+ */
+pub struct CalibrationDocument {
+    lines: Vec<(usize, String)>,
+}
+
+impl CalibrationDocument {
+    /**
+     * The sum of the calibration values of all lines, reading them with `vocabulary`
+     */
+    pub fn sum(&self, vocabulary: &Vocabulary) -> Result<Answer, NoSolution> {
+        self.lines
+            .iter()
+            .map(|(line_number, line)| {
+                vocabulary.calibration_value(line).ok_or_else(|| {
+                    NoSolution(format!("Could not find a number in line {line_number}"))
+                })
+            })
+            .sum::<Result<usize, _>>()
+            .map(Answer::from)
+    }
+}
+
+impl Solver<'_> for CalibrationDocument {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Trebuchet?!";
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines = parse_lines(
+            input,
+            "The calibration document is empty",
+            |line, line_number| Ok((line_number, line.trim_start().to_owned())),
+        )?;
+        Ok(Self { lines })
+    }
+
+    fn part_1(&self) -> Result<Answer, NoSolution> {
+        self.sum(&Vocabulary::digits())
+    }
+
+    fn part_2(&self) -> Result<Answer, NoSolution> {
+        self.sum(&Vocabulary::english())
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::of::<CalibrationDocument>());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlapping_spelled_out_numbers_count_twice() {
+        let english = Vocabulary::english();
+        assert_eq!(english.calibration_value("eightwo"), Some(82));
+        assert_eq!(english.calibration_value("xtwone3four"), Some(24));
+        assert_eq!(english.calibration_value("zoneight234"), Some(14));
+    }
+
+    #[test]
+    fn lines_without_numbers_have_no_solution() {
+        let document = CalibrationDocument::parse("1abc2\n\nseven\n").unwrap();
+        assert_eq!(document.part_2(), Ok(Answer::Int(12 + 77)));
+        assert_eq!(
+            document.part_1(),
+            Err(NoSolution("Could not find a number in line 3".to_owned()))
+        );
+    }
 }
@@ -1,18 +1,96 @@
-use std::{env, fs};
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
 
-use advent_of_code_23_01::multi_line_sum;
+use advent_of_code_23_01::{CalibrationDocument, Vocabulary};
+use aoc_core::{ParseError, Solver};
+use clap::Parser;
+use serde::Serialize;
 
-fn main() {
-    let file_name: String = env::args().nth(1).expect(
-        "You must pass the filename of the challenge input as first argument to this program",
-    );
+/// Show how the calibration value of every line of an Advent of Code 2023 day 01 document
+/// is read
+#[derive(Parser)]
+#[command(
+    version,
+    about,
+    after_help = "Exit codes:
+  0  Success
+  1  The document or the vocabulary could not be read
+  2  Invalid arguments"
+)]
+struct Cli {
+    /// Read the lines like this part does [default: 2]
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2), conflicts_with = "diff")]
+    part: Option<u8>,
 
-    println!("Reading {file_name}");
+    /// Read the tokens of this vocabulary file instead of part 2's English numbers
+    #[arg(long, conflicts_with = "part")]
+    vocabulary: Option<PathBuf>,
 
-    let challenge =
-        fs::read_to_string(file_name).expect("Could not read the file you told me to analyze");
+    /// Only show the lines that part 1 reads differently than part 2
+    #[arg(long)]
+    diff: bool,
 
-    let result_part_1 = multi_line_sum(&challenge);
+    /// Print the lines as JSON, instead of text
+    #[arg(long)]
+    json: bool,
 
-    println!("Result is {result_part_1}");
+    /// The calibration document
+    input: PathBuf,
+}
+
+fn print_lines<T: Display + Serialize>(lines: &[T], json: bool) {
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(lines).expect("Lines are always serializable")
+        );
+    } else {
+        for line in lines {
+            println!("{line}");
+        }
+    }
+}
+
+/**
+ * Like `file:line:column: message` when the error is located, else like `file: message`
+ */
+fn located(path: &Path, error: ParseError) -> String {
+    let separator = if error.line.is_some() { ":" } else { ": " };
+    format!("{}{separator}{error}", path.display())
+}
+
+fn run(cli: Cli) -> Result<(), String> {
+    let challenge = fs::read_to_string(&cli.input)
+        .map_err(|error| format!("Could not read {}: {error}", cli.input.display()))?;
+    let document =
+        CalibrationDocument::parse(&challenge).map_err(|error| located(&cli.input, error))?;
+    let spelled_out = match &cli.vocabulary {
+        Some(path) => Vocabulary::load(path).map_err(|error| located(path, error))?,
+        None => Vocabulary::english(),
+    };
+    if cli.diff {
+        print_lines(
+            &document.diff(&Vocabulary::digits(), &spelled_out),
+            cli.json,
+        );
+    } else if cli.part == Some(1) {
+        print_lines(&document.explain(&Vocabulary::digits()), cli.json);
+    } else {
+        print_lines(&document.explain(&spelled_out), cli.json);
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
 }
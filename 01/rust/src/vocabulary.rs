@@ -0,0 +1,275 @@
+use std::{collections::BTreeMap, fs, ops::Range, path::Path};
+
+use aoc_core::ParseError;
+use serde::{Deserialize, Serialize};
+
+const SPELLED_OUT_NUMBERS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/**
+ * A token of a vocabulary, where it was found in a line
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TokenMatch<'a> {
+    pub token: &'a str,
+    pub value: u8,
+    /**
+     * In bytes
+     */
+    pub range: Range<usize>,
+}
+
+/**
+ * The tokens that count as digits in a calibration line, and which digit each one is
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vocabulary {
+    /**
+     * Longest first, so that the first token found at a position is the longest one
+     */
+    tokens: Vec<(String, u8)>,
+}
+
+/**
+ * A vocabulary file, like `01/vocabularies/german.toml`
+ */
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VocabularyFile {
+    /**
+     * Whether `0` to `9` are tokens too
+     */
+    #[serde(default = "yes")]
+    digits: bool,
+    #[serde(default)]
+    tokens: BTreeMap<Token, Digit>,
+}
+
+fn yes() -> bool {
+    true
+}
+
+#[derive(Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(try_from = "String")]
+struct Token(String);
+
+impl TryFrom<String> for Token {
+    type Error = &'static str;
+
+    fn try_from(token: String) -> Result<Self, Self::Error> {
+        if token.is_empty() {
+            return Err("Tokens cannot be empty");
+        }
+        Ok(Token(token))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(try_from = "u8")]
+struct Digit(u8);
+
+impl TryFrom<u8> for Digit {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if value > 9 {
+            return Err(format!("Expected a digit from 0 to 9, found {value}"));
+        }
+        Ok(Digit(value))
+    }
+}
+
+impl Vocabulary {
+    /**
+     * Fails if a token is empty, is given two different values, or its value is not a
+     * single digit
+     */
+    pub fn new<T: Into<String>>(tokens: impl IntoIterator<Item = (T, u8)>) -> Result<Self, String> {
+        let mut values = BTreeMap::new();
+        for (token, value) in tokens {
+            let token = token.into();
+            if token.is_empty() {
+                return Err("Tokens cannot be empty".to_owned());
+            }
+            if value > 9 {
+                return Err(format!("\"{token}\" is {value}, which is not a digit"));
+            }
+            if let Some(other) = values.insert(token.clone(), value) {
+                if other != value {
+                    return Err(format!("\"{token}\" is both {other} and {value}"));
+                }
+            }
+        }
+        let mut tokens: Vec<_> = values.into_iter().collect();
+        tokens.sort_by_key(|(token, _)| std::cmp::Reverse(token.len()));
+        Ok(Self { tokens })
+    }
+
+    /**
+     * `0` to `9`, for part 1
+     */
+    pub fn digits() -> Self {
+        Self::new((0..=9).map(|digit| (digit.to_string(), digit))).expect("Digits are valid")
+    }
+
+    /**
+     * The digits and `one` to `nine`, for part 2
+     */
+    pub fn english() -> Self {
+        Self::digits()
+            .with(
+                SPELLED_OUT_NUMBERS
+                    .iter()
+                    .zip(1..)
+                    .map(|(word, value)| (*word, value)),
+            )
+            .expect("English numbers are valid")
+    }
+
+    /**
+     * This vocabulary and some more tokens
+     */
+    pub fn with<T: Into<String>>(
+        self,
+        tokens: impl IntoIterator<Item = (T, u8)>,
+    ) -> Result<Self, String> {
+        Self::new(
+            self.tokens.into_iter().chain(
+                tokens
+                    .into_iter()
+                    .map(|(token, value)| (token.into(), value)),
+            ),
+        )
+    }
+
+    /**
+     * Reads a vocabulary file like
+     *
+     * ```toml
+     * digits = true # the default
+     *
+     * [tokens]
+     * eins = 1
+     * ```
+     */
+    pub fn parse(toml: &str) -> Result<Self, ParseError> {
+        let file: VocabularyFile = toml::from_str(toml).map_err(|error| {
+            let parse_error = ParseError::new(error.message());
+            match error.span() {
+                Some(span) => parse_error.at_offset(toml, span.start),
+                None => parse_error,
+            }
+        })?;
+        let base = if file.digits {
+            Self::digits()
+        } else {
+            Self::default()
+        };
+        base.with(
+            file.tokens
+                .into_iter()
+                .map(|(Token(token), Digit(value))| (token, value)),
+        )
+        .map_err(ParseError::new)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ParseError> {
+        let toml = fs::read_to_string(path)
+            .map_err(|error| ParseError::new(format!("Could not read the vocabulary: {error}")))?;
+        Self::parse(&toml)
+    }
+
+    /**
+     * Every token and its value, longest first
+     */
+    pub fn tokens(&self) -> impl Iterator<Item = (&str, u8)> {
+        self.tokens
+            .iter()
+            .map(|(token, value)| (token.as_str(), *value))
+    }
+
+    /**
+     * The token that starts first in `line`, and the longest one if several start there.
+     * Tokens may overlap, like in "twone", so the first and last token are looked for
+     * separately
+     */
+    pub fn first<'a>(&self, line: &'a str) -> Option<TokenMatch<'a>> {
+        line.char_indices().find_map(|(start, _)| {
+            self.tokens()
+                .find(|(token, _)| line[start..].starts_with(token))
+                .map(|(token, value)| TokenMatch {
+                    token: &line[start..start + token.len()],
+                    value,
+                    range: start..start + token.len(),
+                })
+        })
+    }
+
+    /**
+     * The token that ends last in `line`, and the longest one if several end there
+     */
+    pub fn last<'a>(&self, line: &'a str) -> Option<TokenMatch<'a>> {
+        line.char_indices().rev().find_map(|(index, char)| {
+            let end = index + char.len_utf8();
+            self.tokens()
+                .find(|(token, _)| line[..end].ends_with(token))
+                .map(|(token, value)| TokenMatch {
+                    token: &line[end - token.len()..end],
+                    value,
+                    range: end - token.len()..end,
+                })
+        })
+    }
+
+    /**
+     * The two-digit number of the first and the last token. `None` if there is no token in
+     * the line
+     */
+    pub fn calibration_value(&self, line: &str) -> Option<usize> {
+        Some(usize::from(self.first(line)?.value) * 10 + usize::from(self.last(line)?.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_longest_token_at_a_position_wins() {
+        let roman = Vocabulary::parse(include_str!("../../vocabularies/roman.toml")).unwrap();
+        assert_eq!(roman.calibration_value("xVIIIyIVz"), Some(84));
+        assert_eq!(roman.calibration_value("MCMVIII"), Some(88));
+        assert_eq!(roman.calibration_value("1 2 3"), None);
+        let last = roman.last("IV and VII").unwrap();
+        assert_eq!((last.token, last.value, last.range), ("VII", 7, 7..10));
+    }
+
+    #[test]
+    fn vocabularies_are_loaded_from_files() {
+        let german = Vocabulary::parse(include_str!("../../vocabularies/german.toml")).unwrap();
+        assert_eq!(german.calibration_value("xfünfzig3"), Some(53));
+        assert_eq!(german.calibration_value("nullachtfünfzehn"), Some(5));
+        let first = german.first("xfünf").unwrap();
+        // "ü" takes two bytes
+        assert_eq!(first.range, 1..6);
+
+        let zero = Vocabulary::parse("[tokens]\nzero = 0\n").unwrap();
+        assert_eq!(zero.calibration_value("zero1"), Some(1));
+    }
+
+    #[test]
+    fn invalid_vocabularies_are_located() {
+        let error = |toml| Vocabulary::parse(toml).unwrap_err().to_string();
+        assert_eq!(
+            error("[tokens]\nzehn = 10\n"),
+            "2:8: Expected a digit from 0 to 9, found 10"
+        );
+        assert_eq!(error("[tokens]\n\"\" = 1\n"), "2:1: Tokens cannot be empty");
+        assert_eq!(error("[tokens]\n1 = 2\n"), "\"1\" is both 1 and 2");
+        assert_eq!(
+            Vocabulary::english().with([("one", 1), ("ten", 10)]),
+            Err("\"ten\" is 10, which is not a digit".to_owned())
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use aoc_core::{parse_lines, Answer, Day, NoSolution, ParseError, Registry, Solver};
+
+/**
+ * How many cubes of each color there are, in a draw or in the bag
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CubeSet {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+}
+
+impl CubeSet {
+    pub fn new(red: u32, green: u32, blue: u32) -> Self {
+        Self { red, green, blue }
+    }
+
+    /**
+     * Whether a bag with `self` in it could have produced `draw`
+     */
+    pub fn contains(&self, draw: &CubeSet) -> bool {
+        draw.red <= self.red && draw.green <= self.green && draw.blue <= self.blue
+    }
+
+    /**
+     * The fewest cubes of each color that both sets fit into
+     */
+    pub fn union(&self, other: &CubeSet) -> CubeSet {
+        CubeSet::new(
+            self.red.max(other.red),
+            self.green.max(other.green),
+            self.blue.max(other.blue),
+        )
+    }
+
+    pub fn power(&self) -> u64 {
+        u64::from(self.red) * u64::from(self.green) * u64::from(self.blue)
+    }
+
+    fn count_mut(&mut self, color: &str) -> Option<&mut u32> {
+        match color {
+            "red" => Some(&mut self.red),
+            "green" => Some(&mut self.green),
+            "blue" => Some(&mut self.blue),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub draws: Vec<CubeSet>,
+}
+
+impl Game {
+    pub fn is_possible_with(&self, bag: &CubeSet) -> bool {
+        self.draws.iter().all(|draw| bag.contains(draw))
+    }
+
+    /**
+     * The smallest bag this game could have been played with
+     */
+    pub fn minimum_bag(&self) -> CubeSet {
+        self.draws
+            .iter()
+            .fold(CubeSet::default(), |bag, draw| bag.union(draw))
+    }
+
+    /**
+     * Parses a line like `Game 1: 3 blue, 4 red; 1 red, 2 green`
+     */
+    pub fn parse(line: &str, line_number: usize) -> Result<Self, ParseError> {
+        let mut cursor = Cursor {
+            line,
+            line_number,
+            position: 0,
+        };
+        cursor.expect("Game ")?;
+        let id = cursor.number()?;
+        cursor.expect(":")?;
+        let mut draws = vec![cursor.draw()?];
+        while cursor.eat(";") {
+            draws.push(cursor.draw()?);
+        }
+        cursor.skip_spaces();
+        if cursor.position < line.len() {
+            return Err(cursor.error("Expected \";\" or the end of the line"));
+        }
+        Ok(Self { id, draws })
+    }
+}
+
+/**
+ * Walks through one line of the game record, to report where it is malformed
+ */
+struct Cursor<'a> {
+    line: &'a str,
+    line_number: usize,
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn rest(&self) -> &'a str {
+        &self.line[self.position..]
+    }
+
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(message).at(
+            self.line_number,
+            self.line[..self.position].chars().count() + 1,
+        )
+    }
+
+    fn skip_spaces(&mut self) {
+        self.position = self.line.len() - self.rest().trim_start_matches(' ').len();
+    }
+
+    fn eat(&mut self, literal: &str) -> bool {
+        let found = self.rest().starts_with(literal);
+        if found {
+            self.position += literal.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.eat(literal) {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected \"{literal}\"")))
+        }
+    }
+
+    fn take_while(&mut self, matches: impl Fn(char) -> bool) -> &'a str {
+        let start = self.position;
+        let length = self
+            .rest()
+            .find(|char| !matches(char))
+            .unwrap_or(self.rest().len());
+        self.position += length;
+        &self.line[start..self.position]
+    }
+
+    fn number(&mut self) -> Result<u32, ParseError> {
+        let start = self.position;
+        let digits = self.take_while(|char| char.is_ascii_digit());
+        if digits.is_empty() {
+            return Err(self.error("Expected a number"));
+        }
+        digits.parse().map_err(|_| {
+            self.position = start;
+            self.error(format!("{digits} is too large"))
+        })
+    }
+
+    /**
+     * Like ` 3 blue, 4 red`
+     */
+    fn draw(&mut self) -> Result<CubeSet, ParseError> {
+        let mut draw = CubeSet::default();
+        let mut drawn = vec![];
+        loop {
+            self.skip_spaces();
+            let count = self.number()?;
+            self.expect(" ")?;
+            let start = self.position;
+            let color = self.take_while(|char| char.is_ascii_alphabetic());
+            let Some(cubes) = draw.count_mut(color) else {
+                self.position = start;
+                return Err(self.error(format!(
+                    "Expected \"red\", \"green\" or \"blue\", found \"{color}\""
+                )));
+            };
+            if drawn.contains(&color) {
+                self.position = start;
+                return Err(self.error(format!("{color} was already drawn")));
+            }
+            *cubes = count;
+            drawn.push(color);
+            if !self.eat(",") {
+                return Ok(draw);
+            }
+        }
+    }
+}
+
+/**
+ * The bag the elf asks about in part 1
+ */
+pub const BAG: CubeSet = CubeSet {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+/**
+ * The record of all games played, and the bag part 1 asks about
+ */
+pub struct CubeGame {
+    pub games: Vec<Game>,
+    pub bag: CubeSet,
+}
+
+impl CubeGame {
+    pub fn with_bag(self, bag: CubeSet) -> Self {
+        Self { bag, ..self }
+    }
+
+    pub fn sum_of_possible_ids(&self) -> u64 {
+        self.games
+            .iter()
+            .filter(|game| game.is_possible_with(&self.bag))
+            .map(|game| u64::from(game.id))
+            .sum()
+    }
+
+    pub fn sum_of_minimum_powers(&self) -> u64 {
+        self.games
+            .iter()
+            .map(|game| game.minimum_bag().power())
+            .sum()
+    }
+}
+
+impl Solver<'_> for CubeGame {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Cube Conundrum";
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let games = parse_lines(input, "No game was played", Game::parse)?;
+        Ok(Self { games, bag: BAG })
+    }
+
+    fn part_1(&self) -> Result<Answer, NoSolution> {
+        Ok(self.sum_of_possible_ids().into())
+    }
+
+    fn part_2(&self) -> Result<Answer, NoSolution> {
+        Ok(self.sum_of_minimum_powers().into())
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::of::<CubeGame>());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/example.txt");
+
+    #[test]
+    fn games_are_parsed() {
+        assert_eq!(
+            Game::parse("Game 12: 3 blue, 4 red; 2 green", 1),
+            Ok(Game {
+                id: 12,
+                draws: vec![CubeSet::new(4, 0, 3), CubeSet::new(0, 2, 0)]
+            })
+        );
+    }
+
+    #[test]
+    fn malformed_games_are_located() {
+        let error = |line| Game::parse(line, 7).unwrap_err().to_string();
+        assert_eq!(error("Gam 1: 3 blue"), "7:1: Expected \"Game \"");
+        assert_eq!(error("Game 1: 3 blue, x red"), "7:17: Expected a number");
+        assert_eq!(
+            error("Game 1: 3 blue; 4 pink"),
+            "7:19: Expected \"red\", \"green\" or \"blue\", found \"pink\""
+        );
+        assert_eq!(
+            error("Game 1: 3 blue, 4 blue"),
+            "7:19: blue was already drawn"
+        );
+        assert_eq!(
+            error("Game 1: 3 blue | 4 red"),
+            "7:16: Expected \";\" or the end of the line"
+        );
+    }
+
+    #[test]
+    fn the_bag_can_be_changed() {
+        let game = CubeGame::parse(EXAMPLE).unwrap();
+        assert_eq!(game.sum_of_possible_ids(), 8);
+        assert_eq!(
+            game.with_bag(CubeSet::new(20, 20, 20))
+                .sum_of_possible_ids(),
+            15
+        );
+    }
+}
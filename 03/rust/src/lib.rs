@@ -0,0 +1,266 @@
+use std::{collections::BTreeSet, ops::Range};
+
+use aoc_core::{Answer, Coord, Day, Grid, NoSolution, ParseError, Registry, Solver};
+use colored::Colorize;
+
+/**
+ * A number in the schematic, which may span several columns of one row
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberSpan {
+    pub value: u64,
+    pub row: usize,
+    pub columns: Range<usize>,
+}
+
+/**
+ * Anything that is neither a digit nor a `.`
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub char: char,
+    pub at: Coord,
+}
+
+/**
+ * The engine schematic, with its numbers and symbols extracted once
+ */
+pub struct Schematic {
+    grid: Grid<char>,
+    pub numbers: Vec<NumberSpan>,
+    pub symbols: Vec<Symbol>,
+    /**
+     * Which of the `numbers` covers each cell, if any
+     */
+    number_at: Grid<Option<usize>>,
+}
+
+impl Schematic {
+    /**
+     * Fails at the first digit of a number that does not fit into a u64
+     */
+    fn new(grid: Grid<char>) -> Result<Self, ParseError> {
+        let mut numbers: Vec<NumberSpan> = vec![];
+        let mut symbols = vec![];
+        let mut number_at = grid.map(|_, _| None);
+        for (at, char) in grid.iter() {
+            let Some(digit) = char.to_digit(10) else {
+                if *char != '.' {
+                    symbols.push(Symbol { char: *char, at });
+                }
+                continue;
+            };
+            match numbers.last_mut() {
+                Some(number) if number.row == at.row && number.columns.end == at.column => {
+                    number.value = number
+                        .value
+                        .checked_mul(10)
+                        .and_then(|value| value.checked_add(u64::from(digit)))
+                        .ok_or_else(|| {
+                            ParseError::new("This number does not fit into 64 bits")
+                                .at(number.row + 1, number.columns.start + 1)
+                        })?;
+                    number.columns.end += 1;
+                }
+                _ => numbers.push(NumberSpan {
+                    value: u64::from(digit),
+                    row: at.row,
+                    columns: at.column..at.column + 1,
+                }),
+            }
+            number_at[at] = Some(numbers.len() - 1);
+        }
+        Ok(Self {
+            grid,
+            numbers,
+            symbols,
+            number_at,
+        })
+    }
+
+    fn indices_touching(&self, at: Coord) -> BTreeSet<usize> {
+        self.grid
+            .neighbours_8(at)
+            .filter_map(|neighbour| self.number_at[neighbour])
+            .collect()
+    }
+
+    /**
+     * The numbers around `at`, including diagonally, in the order they appear
+     */
+    pub fn numbers_touching(&self, at: Coord) -> Vec<&NumberSpan> {
+        self.indices_touching(at)
+            .into_iter()
+            .map(|index| &self.numbers[index])
+            .collect()
+    }
+
+    pub fn symbol_at(&self, at: Coord) -> Option<&Symbol> {
+        self.symbols.iter().find(|symbol| symbol.at == at)
+    }
+
+    /**
+     * The numbers next to any symbol, each once, in the order they appear
+     */
+    pub fn part_numbers(&self) -> Vec<&NumberSpan> {
+        self.part_number_indices()
+            .into_iter()
+            .map(|index| &self.numbers[index])
+            .collect()
+    }
+
+    fn part_number_indices(&self) -> BTreeSet<usize> {
+        self.symbols
+            .iter()
+            .flat_map(|symbol| self.indices_touching(symbol.at))
+            .collect()
+    }
+
+    /**
+     * Every `*` next to exactly two numbers, with these numbers
+     */
+    pub fn gears(&self) -> Vec<(&Symbol, [&NumberSpan; 2])> {
+        self.symbols
+            .iter()
+            .filter(|symbol| symbol.char == '*')
+            .filter_map(|symbol| match self.numbers_touching(symbol.at)[..] {
+                [first, second] => Some((symbol, [first, second])),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /**
+     * The schematic with part numbers in green, the other numbers in red and gears in bold
+     */
+    pub fn highlighted(&self) -> String {
+        let part_numbers = self.part_number_indices();
+        let gears: Vec<_> = self.gears().iter().map(|(gear, _)| gear.at).collect();
+        self.grid
+            .highlighted(|at, _, cell| match self.number_at[at] {
+                Some(index) if part_numbers.contains(&index) => cell.green(),
+                Some(_) => cell.red(),
+                None if gears.contains(&at) => cell.bold(),
+                None => cell,
+            })
+    }
+}
+
+impl Solver<'_> for Schematic {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Gear Ratios";
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Grid::parse(input).and_then(Self::new)
+    }
+
+    fn part_1(&self) -> Result<Answer, NoSolution> {
+        self.part_numbers()
+            .iter()
+            .try_fold(0u64, |sum, number| sum.checked_add(number.value))
+            .map(Answer::from)
+            .ok_or_else(|| {
+                NoSolution("The sum of the part numbers does not fit into 64 bits".to_owned())
+            })
+    }
+
+    fn part_2(&self) -> Result<Answer, NoSolution> {
+        self.gears()
+            .iter()
+            .try_fold(0u64, |sum, (_, [first, second])| {
+                sum.checked_add(first.value.checked_mul(second.value)?)
+            })
+            .map(Answer::from)
+            .ok_or_else(|| {
+                NoSolution("The sum of the gear ratios does not fit into 64 bits".to_owned())
+            })
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::of::<Schematic>());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/example.txt");
+
+    #[test]
+    fn numbers_span_columns() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        assert_eq!(schematic.numbers.len(), 10);
+        assert_eq!(
+            schematic.numbers[1],
+            NumberSpan {
+                value: 114,
+                row: 0,
+                columns: 5..8
+            }
+        );
+        assert_eq!(schematic.symbols.len(), 6);
+    }
+
+    #[test]
+    fn numbers_touching_a_symbol_can_be_queried() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        let star = Coord::new(1, 3);
+        assert_eq!(
+            schematic.symbol_at(star).map(|symbol| symbol.char),
+            Some('*')
+        );
+        assert_eq!(
+            schematic
+                .numbers_touching(star)
+                .iter()
+                .map(|number| number.value)
+                .collect::<Vec<_>>(),
+            vec![467, 35]
+        );
+        assert!(schematic.numbers_touching(Coord::new(0, 9)).is_empty());
+    }
+
+    #[test]
+    fn numbers_end_at_the_end_of_a_row() {
+        let schematic = Schematic::parse("..12\n34*.\n").unwrap();
+        assert_eq!(
+            schematic
+                .numbers
+                .iter()
+                .map(|number| number.value)
+                .collect::<Vec<_>>(),
+            vec![12, 34]
+        );
+        assert_eq!(schematic.part_2(), Ok(Answer::Int(12 * 34)));
+    }
+
+    #[test]
+    fn overflows_are_refused() {
+        assert_eq!(
+            Schematic::parse(&format!(
+                "{}\n1111111111111111111111*1111111111111111111111\n",
+                ".".repeat(45)
+            ))
+            .map(|_| ())
+            .unwrap_err()
+            .to_string(),
+            "2:1: This number does not fit into 64 bits"
+        );
+        let schematic = Schematic::parse("9999999999999999999*9999999999999999999\n").unwrap();
+        assert_eq!(
+            schematic.part_1(),
+            Err(NoSolution(
+                "The sum of the part numbers does not fit into 64 bits".to_owned()
+            ))
+        );
+        let schematic = Schematic::parse("4294967296*4294967296\n").unwrap();
+        assert_eq!(schematic.part_1(), Ok(Answer::Int(2 * 4294967296)));
+        assert_eq!(
+            schematic.part_2(),
+            Err(NoSolution(
+                "The sum of the gear ratios does not fit into 64 bits".to_owned()
+            ))
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use std::{collections::VecDeque, io::BufRead};
+
+use aoc_core::{parse_lines, Answer, Day, NoSolution, ParseError, Registry, Solver};
+
+/**
+ * A set of numbers below 128, one bit each
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NumberSet(u128);
+
+impl NumberSet {
+    pub const LIMIT: u32 = u128::BITS;
+
+    /**
+     * `false` if `number` is too large to be stored
+     */
+    pub fn insert(&mut self, number: u32) -> bool {
+        if number >= Self::LIMIT {
+            return false;
+        }
+        self.0 |= 1 << number;
+        true
+    }
+
+    pub fn contains(&self, number: u32) -> bool {
+        number < Self::LIMIT && self.0 & (1 << number) != 0
+    }
+
+    pub fn intersection(&self, other: &NumberSet) -> NumberSet {
+        NumberSet(self.0 & other.0)
+    }
+
+    pub fn len(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub id: u32,
+    pub winning: NumberSet,
+    pub numbers: NumberSet,
+}
+
+impl Card {
+    /**
+     * Parses a line like `Card 1: 41 48 | 83 41 6`
+     */
+    pub fn parse(line: &str, line_number: usize) -> Result<Self, ParseError> {
+        let error = |message: String| ParseError::new(message).at_line(line_number);
+        let (name, numbers) = line
+            .split_once(':')
+            .ok_or_else(|| error("Expected \"Card <id>: <numbers> | <numbers>\"".to_owned()))?;
+        let id = name
+            .strip_prefix("Card")
+            .and_then(|id| id.trim().parse().ok())
+            .ok_or_else(|| error(format!("Expected \"Card <id>\", found \"{name}\"")))?;
+        let (winning, numbers) = numbers
+            .split_once('|')
+            .ok_or_else(|| error("Expected a \"|\" between the numbers".to_owned()))?;
+        let number_set = |numbers: &str| {
+            let mut set = NumberSet::default();
+            for number in numbers.split_whitespace() {
+                match number.parse() {
+                    Ok(number) if set.insert(number) => {}
+                    _ => {
+                        return Err(error(format!(
+                            "Expected a number below {}, found \"{number}\"",
+                            NumberSet::LIMIT
+                        )))
+                    }
+                }
+            }
+            Ok(set)
+        };
+        Ok(Self {
+            id,
+            winning: number_set(winning)?,
+            numbers: number_set(numbers)?,
+        })
+    }
+
+    pub fn matches(&self) -> u32 {
+        self.winning.intersection(&self.numbers).len()
+    }
+
+    /**
+     * `None` if they do not fit into a `u64`, which takes 65 matches or more
+     */
+    pub fn points(&self) -> Option<u64> {
+        points(self.matches())
+    }
+}
+
+/**
+ * The first match is worth one point, every further one doubles them
+ */
+fn points(matches: u32) -> Option<u64> {
+    match matches {
+        0 => Some(0),
+        matches => 1u64.checked_shl(matches - 1),
+    }
+}
+
+const TOO_MANY_POINTS: &str = "The cards are worth too many points to count";
+const TOO_MANY_CARDS: &str = "There are too many copies of the cards to count";
+
+/**
+ * Counts how many copies of each card we end up with, one card after the other.
+ * Only remembers the copies won for the cards that are still to come
+ */
+#[derive(Debug)]
+pub struct CopyCounter {
+    /**
+     * The extra copies of the next cards, starting with the next one
+     */
+    won: VecDeque<u64>,
+    /**
+     * `None` once a count did not fit into a u64
+     */
+    total: Option<u64>,
+}
+
+impl Default for CopyCounter {
+    fn default() -> Self {
+        Self {
+            won: VecDeque::new(),
+            total: Some(0),
+        }
+    }
+}
+
+impl CopyCounter {
+    /**
+     * Scratches the next card, which has `matches` winning numbers. Returns how many copies
+     * of it we have, including the original. Copies can double with every card, so this is
+     * `None` once they do not fit into a u64, and for every card after that
+     */
+    pub fn scratch(&mut self, matches: u32) -> Option<u64> {
+        let copies = self.count_copies(matches);
+        if copies.is_none() {
+            self.total = None;
+        }
+        copies
+    }
+
+    fn count_copies(&mut self, matches: u32) -> Option<u64> {
+        let total = self.total?;
+        let copies = self.won.pop_front().unwrap_or(0).checked_add(1)?;
+        let matches = matches as usize;
+        if self.won.len() < matches {
+            self.won.resize(matches, 0);
+        }
+        for won in self.won.iter_mut().take(matches) {
+            *won = won.checked_add(copies)?;
+        }
+        self.total = Some(total.checked_add(copies)?);
+        Some(copies)
+    }
+
+    /**
+     * All cards scratched so far, copies included. Cards won beyond the last card scratched
+     * do not exist, so they are not counted. `None` if they do not fit into a u64
+     */
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+}
+
+/**
+ * The answers to both parts
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Totals {
+    /**
+     * `None` if they do not fit into a `u64`
+     */
+    pub points: Option<u64>,
+    /**
+     * `None` if they do not fit into a `u64`
+     */
+    pub cards: Option<u64>,
+}
+
+/**
+ * Answers both parts in one pass over `reader`, without keeping the cards in memory
+ */
+pub fn scratch(reader: impl BufRead) -> Result<Totals, ParseError> {
+    let mut points = Some(0u64);
+    let mut counter = CopyCounter::default();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|error| ParseError::new(error.to_string()).at_line(index + 1))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let card = Card::parse(&line, index + 1)?;
+        points = points
+            .zip(card.points())
+            .and_then(|(points, more)| points.checked_add(more));
+        counter.scratch(card.matches());
+    }
+    Ok(Totals {
+        points,
+        cards: counter.total(),
+    })
+}
+
+/**
+ * The pile of scratchcards, by how many winning numbers each card has
+ */
+pub struct Scratchcards {
+    pub matches: Vec<u32>,
+}
+
+impl Solver<'_> for Scratchcards {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Scratchcards";
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let matches = parse_lines(input, "There are no scratchcards", |line, line_number| {
+            Card::parse(line, line_number).map(|card| card.matches())
+        })?;
+        Ok(Self { matches })
+    }
+
+    fn part_1(&self) -> Result<Answer, NoSolution> {
+        self.matches
+            .iter()
+            .try_fold(0u64, |total, matches| total.checked_add(points(*matches)?))
+            .map(Answer::from)
+            .ok_or_else(|| NoSolution(TOO_MANY_POINTS.to_owned()))
+    }
+
+    fn part_2(&self) -> Result<Answer, NoSolution> {
+        let mut counter = CopyCounter::default();
+        for matches in &self.matches {
+            counter.scratch(*matches);
+        }
+        counter
+            .total()
+            .map(Answer::from)
+            .ok_or_else(|| NoSolution(TOO_MANY_CARDS.to_owned()))
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::of::<Scratchcards>());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/example.txt");
+
+    #[test]
+    fn cards_are_parsed_into_bitsets() {
+        let card = Card::parse("Card  3:  1 21 | 69 1  99", 3).unwrap();
+        assert_eq!(card.id, 3);
+        assert!(card.winning.contains(21) && !card.winning.contains(69));
+        assert_eq!(card.numbers.len(), 3);
+        assert_eq!(card.matches(), 1);
+        assert_eq!(
+            Card::parse("Card 1: 1 | 128", 5).unwrap_err().to_string(),
+            "5: Expected a number below 128, found \"128\""
+        );
+        assert!(Card::parse("Card 1: 1 2 3", 1).is_err());
+    }
+
+    #[test]
+    fn copies_are_counted_while_streaming() {
+        let mut counter = CopyCounter::default();
+        let copies: Vec<_> = [4, 2, 2, 1, 0, 0]
+            .into_iter()
+            .map(|matches| counter.scratch(matches).unwrap())
+            .collect();
+        assert_eq!(copies, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(counter.total(), Some(30));
+    }
+
+    #[test]
+    fn reading_streams_both_parts() {
+        assert_eq!(
+            scratch(EXAMPLE.as_bytes()),
+            Ok(Totals {
+                points: Some(13),
+                cards: Some(30)
+            })
+        );
+    }
+
+    #[test]
+    fn too_many_points_have_no_solution() {
+        let numbers = (0..100)
+            .map(|number| number.to_string())
+            .collect::<Vec<_>>();
+        let card = format!("Card 1: {0} | {0}", numbers.join(" "));
+        assert_eq!(Card::parse(&card, 1).unwrap().matches(), 100);
+        assert_eq!(Card::parse(&card, 1).unwrap().points(), None);
+
+        let scratchcards = Scratchcards::parse(&card).unwrap();
+        assert_eq!(
+            scratchcards.part_1(),
+            Err(NoSolution(TOO_MANY_POINTS.to_owned()))
+        );
+        assert_eq!(scratchcards.part_2(), Ok(Answer::Int(1)));
+        assert_eq!(scratch(card.as_bytes()).unwrap().points, None);
+
+        // 64 matches are the most that still fit
+        let card = format!("Card 1: {0} | {0}", numbers[..64].join(" "));
+        assert_eq!(Card::parse(&card, 1).unwrap().points(), Some(1 << 63));
+
+        // Every card doubles the copies of all that follow, so card 65 has 2^64 of them
+        let cards = (1..=80)
+            .map(|id| format!("Card {id}: {0} | {0}", numbers.join(" ")))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let scratchcards = Scratchcards::parse(&cards).unwrap();
+        assert_eq!(
+            scratchcards.part_2(),
+            Err(NoSolution(TOO_MANY_CARDS.to_owned()))
+        );
+        assert_eq!(scratch(cards.as_bytes()).unwrap().cards, None);
+        let mut counter = CopyCounter::default();
+        assert_eq!(counter.scratch(100), Some(1));
+        assert!((2..=64).all(|_| counter.scratch(100).is_some()));
+        assert_eq!(counter.scratch(100), None);
+        assert_eq!(counter.scratch(0), None);
+        assert_eq!(counter.total(), None);
+    }
+}
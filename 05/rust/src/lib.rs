@@ -0,0 +1,273 @@
+use std::{ops::Range, sync::OnceLock};
+
+use aoc_core::{Answer, Day, IntervalSet, NoSolution, ParseError, RangeMap, Registry, Solver};
+
+/**
+ * One of the almanac's maps, like `seed-to-soil`
+ */
+#[derive(Debug, Clone)]
+pub struct Mapping {
+    pub from: String,
+    pub to: String,
+    pub map: RangeMap,
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    pub seeds: Vec<i64>,
+    /**
+     * In order, each one mapping to what the next one maps from
+     */
+    pub mappings: Vec<Mapping>,
+    /**
+     * All mappings composed into one. Created by `prepare`, or when it is first needed
+     */
+    seed_to_location: OnceLock<RangeMap>,
+}
+
+impl Almanac {
+    pub fn seed_to_location(&self) -> &RangeMap {
+        self.seed_to_location.get_or_init(|| {
+            self.mappings
+                .iter()
+                .fold(RangeMap::default(), |composed, mapping| {
+                    composed.compose(&mapping.map)
+                })
+        })
+    }
+
+    /**
+     * Part 2 reads the seeds as pairs of start and length
+     */
+    pub fn seed_ranges(&self) -> Result<IntervalSet, NoSolution> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(NoSolution(
+                "The seeds do not come in pairs of start and length".to_owned(),
+            ));
+        }
+        self.seeds
+            .chunks(2)
+            .map(|pair| seed_range(pair[0], pair[1]))
+            .collect::<Option<Vec<_>>>()
+            .map(IntervalSet::new)
+            .ok_or_else(|| NoSolution(SEED_RANGE.to_owned()))
+    }
+}
+
+const SEED_RANGE: &str = "Expected seed ranges with a length of at least 0 that fit into 64 bits";
+
+/**
+ * `None` if the length is negative, or the range ends beyond `i64::MAX`
+ */
+fn seed_range(start: i64, length: i64) -> Option<Range<i64>> {
+    if length < 0 {
+        return None;
+    }
+    Some(start..start.checked_add(length)?)
+}
+
+fn parse_numbers(numbers: &str, line_number: usize) -> Result<Vec<i64>, ParseError> {
+    numbers
+        .split_whitespace()
+        .map(|number| {
+            number.parse().map_err(|_| {
+                ParseError::new(format!("Expected a number, found \"{number}\""))
+                    .at_line(line_number)
+            })
+        })
+        .collect()
+}
+
+impl Solver<'_> for Almanac {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()));
+        let seeds = match lines.next() {
+            Some((line_number, line)) => match line.strip_prefix("seeds:") {
+                Some(seeds) => {
+                    let seeds = parse_numbers(seeds, line_number)?;
+                    // Part 2 reads them as ranges
+                    if let Some([start, length]) = seeds
+                        .as_chunks::<2>()
+                        .0
+                        .iter()
+                        .find(|[start, length]| seed_range(*start, *length).is_none())
+                    {
+                        return Err(ParseError::new(format!(
+                            "{SEED_RANGE}, found {start} {length}"
+                        ))
+                        .at_line(line_number));
+                    }
+                    seeds
+                }
+                None => {
+                    return Err(
+                        ParseError::new("Expected \"seeds: <numbers>\"").at_line(line_number)
+                    )
+                }
+            },
+            None => return Err(ParseError::new("The almanac is empty")),
+        };
+
+        let mut mappings: Vec<Mapping> = vec![];
+        let mut entries = vec![];
+        // Overlapping ranges are reported at the header of their map
+        let mut header_line = 0;
+        for (line_number, line) in lines.filter(|(_, line)| !line.is_empty()) {
+            if let Some(name) = line.strip_suffix(" map:") {
+                let Some((from, to)) = name.split_once("-to-") else {
+                    return Err(ParseError::new(format!(
+                        "Expected \"<from>-to-<to> map:\", found \"{line}\""
+                    ))
+                    .at_line(line_number));
+                };
+                let expected = mappings.last().map_or("seed", |mapping| &mapping.to);
+                if from != expected {
+                    return Err(ParseError::new(format!(
+                        "Expected a map from {expected}, found one from {from}"
+                    ))
+                    .at_line(line_number));
+                }
+                if let Some(previous) = mappings.last_mut() {
+                    previous.map = RangeMap::new(entries.drain(..))
+                        .map_err(|error| ParseError::new(error).at_line(header_line))?;
+                }
+                header_line = line_number;
+                mappings.push(Mapping {
+                    from: from.to_owned(),
+                    to: to.to_owned(),
+                    map: RangeMap::default(),
+                });
+                continue;
+            }
+            if mappings.is_empty() {
+                return Err(
+                    ParseError::new("Expected a map before its ranges").at_line(line_number)
+                );
+            }
+            match parse_numbers(line, line_number)?[..] {
+                [destination, source, length] => {
+                    RangeMap::check_entry(destination, source, length)
+                        .map_err(|error| ParseError::new(error).at_line(line_number))?;
+                    entries.push((destination, source, length))
+                }
+                _ => {
+                    return Err(ParseError::new(
+                        "Expected the destination start, source start and length",
+                    )
+                    .at_line(line_number))
+                }
+            }
+        }
+        let Some(last) = mappings.last_mut() else {
+            return Err(ParseError::new("The almanac has no maps"));
+        };
+        last.map =
+            RangeMap::new(entries).map_err(|error| ParseError::new(error).at_line(header_line))?;
+
+        Ok(Self {
+            seeds,
+            mappings,
+            seed_to_location: OnceLock::new(),
+        })
+    }
+
+    fn prepare(&mut self) {
+        self.seed_to_location();
+    }
+
+    fn part_1(&self) -> Result<Answer, NoSolution> {
+        self.seeds
+            .iter()
+            .map(|seed| self.seed_to_location().apply(*seed))
+            .min()
+            .map(Answer::from)
+            .ok_or_else(|| NoSolution("There are no seeds".to_owned()))
+    }
+
+    fn part_2(&self) -> Result<Answer, NoSolution> {
+        self.seed_to_location()
+            .apply_intervals(&self.seed_ranges()?)
+            .min()
+            .map(Answer::from)
+            .ok_or_else(|| NoSolution("There are no seeds".to_owned()))
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::of::<Almanac>());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/example.txt");
+
+    #[test]
+    fn the_composed_map_is_the_chain_of_maps() {
+        let almanac = Almanac::parse(EXAMPLE).unwrap();
+        assert_eq!(almanac.mappings.len(), 7);
+        assert_eq!(almanac.mappings[6].to, "location");
+        for seed in 0..120 {
+            let chained = almanac
+                .mappings
+                .iter()
+                .fold(seed, |value, mapping| mapping.map.apply(value));
+            assert_eq!(almanac.seed_to_location().apply(seed), chained, "{seed}");
+        }
+    }
+
+    #[test]
+    fn seed_ranges_are_not_enumerated() {
+        let almanac =
+            Almanac::parse("seeds: 0 4000000000\n\nseed-to-location map:\n10 0 5\n").unwrap();
+        assert_eq!(almanac.seed_ranges().unwrap().len(), 4_000_000_000);
+        assert_eq!(almanac.part_2(), Ok(Answer::Int(5)));
+    }
+
+    #[test]
+    fn broken_chains_are_rejected() {
+        let error = Almanac::parse("seeds: 1\n\nseed-to-soil map:\n1 2 3\n\nwater-to-light map:\n")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "6: Expected a map from soil, found one from water"
+        );
+        let error = Almanac::parse("seeds: 1\n\nseed-to-soil map:\n1 2\n").unwrap_err();
+        assert_eq!(error.line, Some(4));
+        let error = Almanac::parse("seeds: 1\n\nseed-to-soil map:\n1 2 3\n9 3 3\n").unwrap_err();
+        assert_eq!(error.to_string(), "3: 2..5 overlaps with 3..6");
+    }
+
+    #[test]
+    fn lengths_are_checked_where_they_are_read() {
+        let error = |input| Almanac::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            error("seeds: 1\n\nseed-to-soil map:\n1 2 3\n1 9 -3\n"),
+            "5: The length -3 is negative"
+        );
+        assert_eq!(
+            error("seeds: 1\n\nseed-to-soil map:\n9223372036854775807 2 3\n"),
+            "4: The ranges of 9223372036854775807 2 3 do not fit into 64 bits"
+        );
+        assert_eq!(
+            error("seeds: 1 2 3 -4\n\nseed-to-soil map:\n1 2 3\n"),
+            format!("1: {SEED_RANGE}, found 3 -4")
+        );
+        assert_eq!(
+            error("seeds: 9223372036854775807 1\n\nseed-to-soil map:\n1 2 3\n"),
+            format!("1: {SEED_RANGE}, found 9223372036854775807 1")
+        );
+        // Part 1 reads them as seeds, so only part 2 needs them to be pairs
+        let almanac = Almanac::parse("seeds: 1 -2 3\n\nseed-to-soil map:\n1 2 3\n");
+        assert!(almanac.is_err());
+        let almanac = Almanac::parse("seeds: -2 3 4\n\nseed-to-soil map:\n1 2 3\n").unwrap();
+        assert_eq!(almanac.part_1(), Ok(Answer::Int(-2)));
+    }
+}
@@ -0,0 +1,259 @@
+use std::ops::Range;
+
+use aoc_core::{Answer, Day, NoSolution, ParseError, Registry, Solver};
+
+/**
+ * One race: how long it lasts, and the distance to beat
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time: u64,
+    pub record: u64,
+}
+
+impl Race {
+    pub fn new(time: u64, record: u64) -> Self {
+        Self { time, record }
+    }
+
+    /**
+     * How far the boat gets when the button is held for `hold` milliseconds
+     */
+    pub fn distance(&self, hold: u64) -> u128 {
+        u128::from(hold) * u128::from(self.time.saturating_sub(hold))
+    }
+
+    /**
+     * All hold times that beat the record. The distance `h * (time - h)` is a parabola,
+     * so these lie between the roots of `h² - time * h + record`. The roots are found with
+     * an integer square root, which is exact where floating point would round
+     */
+    pub fn winning_holds(&self) -> Range<u64> {
+        let time = u128::from(self.time);
+        // Cannot overflow, as `time²` and `4 * record` are both below 2^128
+        let squared = time * time;
+        let record_term = 4 * u128::from(self.record);
+        if squared <= record_term {
+            return 0..0;
+        }
+        // `isqrt` is at most one below the real root, so this is at most one below the
+        // first winning hold, or one above the last losing one
+        let mut first = (time - (squared - record_term).isqrt()) / 2;
+        let half = time / 2;
+        while first <= half && first * (time - first) <= u128::from(self.record) {
+            first += 1;
+        }
+        if first > half {
+            return 0..0;
+        }
+        // Both fit into a u64, as they are at most `time`
+        let first = first as u64;
+        first..self.time - first + 1
+    }
+
+    pub fn ways_to_win(&self) -> u64 {
+        let holds = self.winning_holds();
+        holds.end - holds.start
+    }
+}
+
+/**
+ * The sheet of paper with the races on it
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Races {
+    pub races: Vec<Race>,
+    /**
+     * The one long race of part 2, with the spaces between the numbers ignored. `None` if its
+     * time or record does not fit into 64 bits
+     */
+    pub kerned: Option<Race>,
+}
+
+/**
+ * The numbers after `label` on the numbered `line`, and all their digits as one number if it
+ * fits into a u64
+ */
+fn parse_row(
+    line: Option<(usize, &str)>,
+    label: &str,
+) -> Result<(Vec<u64>, Option<u64>), ParseError> {
+    let error = ParseError::new(format!("Expected \"{label} <numbers>\""));
+    let Some((line_number, line)) = line else {
+        return Err(error);
+    };
+    let Some(numbers) = line.trim().strip_prefix(label) else {
+        return Err(error.at_line(line_number));
+    };
+    let numbers: Vec<&str> = numbers.split_whitespace().collect();
+    let parsed = numbers
+        .iter()
+        .map(|number| {
+            // `parse` would also take a leading `+`, which is no digit to kern
+            number
+                .bytes()
+                .all(|digit| digit.is_ascii_digit())
+                .then(|| number.parse().ok())
+                .flatten()
+                .ok_or_else(|| {
+                    ParseError::new(format!("Expected a number below 2^64, found \"{number}\""))
+                        .at_line(line_number)
+                })
+        })
+        .collect::<Result<Vec<u64>, _>>()?;
+    let kerned =
+        numbers
+            .iter()
+            .flat_map(|number| number.chars())
+            .try_fold(0u64, |kerned, digit| {
+                kerned
+                    .checked_mul(10)?
+                    .checked_add(u64::from(digit.to_digit(10)?))
+            });
+    Ok((parsed, kerned))
+}
+
+impl Solver<'_> for Races {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Wait For It";
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| (index + 1, line));
+        let (times, kerned_time) = parse_row(lines.next(), "Time:")?;
+        let distances = lines.next();
+        let (records, kerned_record) = parse_row(distances, "Distance:")?;
+        if times.len() != records.len() {
+            return Err(ParseError::new(format!(
+                "There are {} times, but {} distances",
+                times.len(),
+                records.len()
+            ))
+            .at_line(distances.map_or(2, |(line_number, _)| line_number)));
+        }
+        if times.is_empty() {
+            return Err(ParseError::new("There are no races"));
+        }
+        Ok(Self {
+            races: times
+                .into_iter()
+                .zip(records)
+                .map(|(time, record)| Race::new(time, record))
+                .collect(),
+            kerned: kerned_time
+                .zip(kerned_record)
+                .map(|(time, record)| Race::new(time, record)),
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer, NoSolution> {
+        self.races
+            .iter()
+            .try_fold(1u128, |product, race| {
+                product.checked_mul(u128::from(race.ways_to_win()))
+            })
+            .filter(|product| i128::try_from(*product).is_ok())
+            .map(Answer::from)
+            .ok_or_else(|| NoSolution("The product does not fit into an i128".to_owned()))
+    }
+
+    fn part_2(&self) -> Result<Answer, NoSolution> {
+        self.kerned
+            .map(|race| race.ways_to_win().into())
+            .ok_or_else(|| NoSolution("The kerned race does not fit into 64 bits".to_owned()))
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::of::<Races>());
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/example.txt");
+
+    fn brute_force(race: &Race) -> u64 {
+        (0..=race.time)
+            .filter(|hold| race.distance(*hold) > u128::from(race.record))
+            .count() as u64
+    }
+
+    #[test]
+    fn races_are_parsed_and_kerned() {
+        let races = Races::parse(EXAMPLE).unwrap();
+        assert_eq!(races.races[2], Race::new(30, 200));
+        assert_eq!(races.kerned, Some(Race::new(71530, 940200)));
+        assert_eq!(races.races[1].winning_holds(), 4..12);
+        assert_eq!(
+            Races::parse("Time: 1 2\nDistance: 3\n")
+                .unwrap_err()
+                .to_string(),
+            "2: There are 2 times, but 1 distances"
+        );
+        // Blank lines are skipped, but still counted
+        assert_eq!(
+            Races::parse("\nTime: 1 2\n\nDistance: 3\n")
+                .unwrap_err()
+                .to_string(),
+            "4: There are 2 times, but 1 distances"
+        );
+        assert_eq!(
+            Races::parse("\nTime: 1\nDistance 3\n")
+                .unwrap_err()
+                .to_string(),
+            "3: Expected \"Distance: <numbers>\""
+        );
+        assert_eq!(
+            Races::parse("Time: 7 +7\nDistance: 9 9\n")
+                .unwrap_err()
+                .to_string(),
+            "1: Expected a number below 2^64, found \"+7\""
+        );
+    }
+
+    #[test]
+    fn ties_and_unwinnable_races_count_nothing() {
+        // 5 * 5 only ties the record
+        assert_eq!(Race::new(10, 25).ways_to_win(), 0);
+        // The roots 1 and 2 have no integer between them
+        assert_eq!(Race::new(3, 2).ways_to_win(), 0);
+        assert_eq!(Race::new(0, 0).ways_to_win(), 0);
+    }
+
+    #[test]
+    fn the_largest_races_do_not_overflow() {
+        assert_eq!(Race::new(u64::MAX, 0).ways_to_win(), u64::MAX - 1);
+        // Holding for 1 only ties a record of `u64::MAX - 1`, but holding for 2 beats it
+        assert_eq!(
+            Race::new(u64::MAX, u64::MAX - 1).ways_to_win(),
+            u64::MAX - 3
+        );
+        assert_eq!(Race::new(u64::MAX, u64::MAX).ways_to_win(), u64::MAX - 3);
+        let races = Races::parse("Time: 18446744073709551615 1\nDistance: 0 0\n").unwrap();
+        assert_eq!(races.kerned, None);
+        assert!(races.part_2().is_err());
+        assert_eq!(races.part_1(), Ok(Answer::Int(0)));
+    }
+
+    proptest! {
+        #[test]
+        fn the_closed_form_agrees_with_brute_force(time in 0u64..300, record in 0u64..25_000) {
+            let race = Race::new(time, record);
+            prop_assert_eq!(race.ways_to_win(), brute_force(&race));
+        }
+
+        #[test]
+        fn records_just_below_the_best_distance_are_exact(time in 0u64..100_000, below in 0u64..3) {
+            let best = (time / 2) * (time - time / 2);
+            let race = Race::new(time, best.saturating_sub(below));
+            prop_assert_eq!(race.ways_to_win(), brute_force(&race));
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use aoc_core::{parse_lines, Answer, Day, NoSolution, ParseError, Registry, Solver};
+
+/**
+ * The card labels, from weakest to strongest under the standard rules
+ */
+pub const LABELS: &str = "23456789TJQKA";
+
+/**
+ * A card, by the position of its label in `LABELS`
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Card(u8);
+
+impl Card {
+    pub const JACK: Card = Card(9);
+
+    pub fn from_label(label: char) -> Option<Self> {
+        LABELS.find(label).map(|index| Card(index as u8))
+    }
+
+    pub fn label(&self) -> char {
+        LABELS.as_bytes()[self.0 as usize] as char
+    }
+}
+
+/**
+ * Ordered from weakest to strongest
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    /**
+     * Only the two largest groups of equal cards matter for a hand of five
+     */
+    pub fn from_groups(largest: u8, second: u8) -> Self {
+        match (largest, second) {
+            (5.., _) => HandType::FiveOfAKind,
+            (4, _) => HandType::FourOfAKind,
+            (3, 2..) => HandType::FullHouse,
+            (3, _) => HandType::ThreeOfAKind,
+            (2, 2..) => HandType::TwoPair,
+            (2, _) => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand {
+    pub cards: [Card; 5],
+    pub bid: u64,
+}
+
+impl Hand {
+    /**
+     * Parses a line like `32T3K 765`
+     */
+    pub fn parse(line: &str, line_number: usize) -> Result<Self, ParseError> {
+        let error =
+            |column: usize, message: String| ParseError::new(message).at(line_number, column);
+        let (labels, bid) = line
+            .split_once(' ')
+            .ok_or_else(|| error(1, "Expected \"<cards> <bid>\"".to_owned()))?;
+        let bid_column = labels.chars().count() + 2;
+        let mut cards = [Card(0); 5];
+        let mut labels = labels.chars();
+        for (index, card) in cards.iter_mut().enumerate() {
+            let label = labels.next();
+            *card = label.and_then(Card::from_label).ok_or_else(|| {
+                let found = label.map_or("a space".to_owned(), |label| format!("\"{label}\""));
+                error(
+                    index + 1,
+                    format!("Expected one of \"{LABELS}\", found {found}"),
+                )
+            })?;
+        }
+        if labels.next().is_some() {
+            return Err(error(6, "Expected only five cards".to_owned()));
+        }
+        let bid = bid
+            .trim()
+            .parse()
+            .map_err(|_| error(bid_column, format!("Expected a bid, found \"{bid}\"")))?;
+        Ok(Self { cards, bid })
+    }
+
+    /**
+     * How many of each card the hand has, indexed like `LABELS`
+     */
+    pub fn counts(&self) -> [u8; LABELS.len()] {
+        let mut counts = [0; LABELS.len()];
+        for card in self.cards {
+            counts[card.0 as usize] += 1;
+        }
+        counts
+    }
+}
+
+/**
+ * The sizes of the two largest groups in `counts`, in one pass over its fixed length
+ */
+fn largest_groups(counts: &[u8]) -> (u8, u8) {
+    counts
+        .iter()
+        .fold((0, 0), |(largest, second), count| match *count {
+            count if count > largest => (count, largest),
+            count if count > second => (largest, count),
+            _ => (largest, second),
+        })
+}
+
+/**
+ * How hands are ranked: first by their type, then card by card
+ */
+pub trait HandRules {
+    fn hand_type(hand: &Hand) -> HandType;
+
+    /**
+     * Must be below 16, to fit into the sort key
+     */
+    fn strength(card: Card) -> u8;
+
+    /**
+     * Orders hands like the rules do: the type in the highest bits, followed by four bits
+     * for each card's strength
+     */
+    fn sort_key(hand: &Hand) -> u32 {
+        hand.cards
+            .iter()
+            .fold(Self::hand_type(hand) as u32, |key, card| {
+                key << 4 | u32::from(Self::strength(*card))
+            })
+    }
+}
+
+/**
+ * The rules of part 1
+ */
+pub struct Standard;
+
+impl HandRules for Standard {
+    fn hand_type(hand: &Hand) -> HandType {
+        let (largest, second) = largest_groups(&hand.counts());
+        HandType::from_groups(largest, second)
+    }
+
+    fn strength(card: Card) -> u8 {
+        card.0
+    }
+}
+
+/**
+ * The rules of part 2: `J` is the weakest card, but joins the largest group of other cards
+ */
+pub struct Jokers;
+
+impl HandRules for Jokers {
+    fn hand_type(hand: &Hand) -> HandType {
+        let mut counts = hand.counts();
+        let jokers = std::mem::take(&mut counts[Card::JACK.0 as usize]);
+        let (largest, second) = largest_groups(&counts);
+        HandType::from_groups(largest + jokers, second)
+    }
+
+    fn strength(card: Card) -> u8 {
+        match card {
+            Card::JACK => 0,
+            card if card < Card::JACK => card.0 + 1,
+            card => card.0,
+        }
+    }
+}
+
+/**
+ * The hands from weakest to strongest, with each key computed once
+ */
+pub fn ranked<R: HandRules>(hands: &[Hand]) -> Vec<&Hand> {
+    let mut keyed: Vec<_> = hands.iter().map(|hand| (R::sort_key(hand), hand)).collect();
+    keyed.sort_unstable_by_key(|(key, _)| *key);
+    keyed.into_iter().map(|(_, hand)| hand).collect()
+}
+
+/**
+ * `None` if they do not fit into 64 bits
+ */
+pub fn total_winnings<R: HandRules>(hands: &[Hand]) -> Option<u64> {
+    ranked::<R>(hands)
+        .iter()
+        .zip(1..)
+        .try_fold(0u64, |total, (hand, rank)| {
+            total.checked_add(hand.bid.checked_mul(rank)?)
+        })
+}
+
+const TOO_MUCH_WINNINGS: &str = "The total winnings do not fit into 64 bits";
+
+pub struct CamelCards {
+    pub hands: Vec<Hand>,
+}
+
+impl Solver<'_> for CamelCards {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Camel Cards";
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let hands = parse_lines(input, "There are no hands", Hand::parse)?;
+        Ok(Self { hands })
+    }
+
+    fn part_1(&self) -> Result<Answer, NoSolution> {
+        total_winnings::<Standard>(&self.hands)
+            .map(Answer::from)
+            .ok_or_else(|| NoSolution(TOO_MUCH_WINNINGS.to_owned()))
+    }
+
+    fn part_2(&self) -> Result<Answer, NoSolution> {
+        total_winnings::<Jokers>(&self.hands)
+            .map(Answer::from)
+            .ok_or_else(|| NoSolution(TOO_MUCH_WINNINGS.to_owned()))
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::of::<CamelCards>());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/example.txt");
+
+    fn hand(cards: &str) -> Hand {
+        Hand::parse(&format!("{cards} 1"), 1).unwrap()
+    }
+
+    #[test]
+    fn hands_are_classified_by_their_groups() {
+        let types = [
+            "AAAAA", "AA8AA", "23332", "TTT98", "23432", "A23A4", "23456",
+        ]
+        .map(|cards| Standard::hand_type(&hand(cards)));
+        assert_eq!(
+            types,
+            [
+                HandType::FiveOfAKind,
+                HandType::FourOfAKind,
+                HandType::FullHouse,
+                HandType::ThreeOfAKind,
+                HandType::TwoPair,
+                HandType::OnePair,
+                HandType::HighCard
+            ]
+        );
+    }
+
+    #[test]
+    fn jokers_join_the_largest_group_but_are_weakest() {
+        assert_eq!(Jokers::hand_type(&hand("KTJJT")), HandType::FourOfAKind);
+        assert_eq!(Jokers::hand_type(&hand("JJJJJ")), HandType::FiveOfAKind);
+        assert_eq!(Jokers::hand_type(&hand("2345J")), HandType::OnePair);
+        assert_eq!(Standard::hand_type(&hand("KTJJT")), HandType::TwoPair);
+        assert!(Jokers::sort_key(&hand("JKKK2")) < Jokers::sort_key(&hand("QQQQ2")));
+        assert!(Jokers::sort_key(&hand("JJJJJ")) < Jokers::sort_key(&hand("22222")));
+    }
+
+    #[test]
+    fn ranking_sorts_by_the_packed_key() {
+        let cards = CamelCards::parse(EXAMPLE).unwrap();
+        let labels = |hands: Vec<&Hand>| -> Vec<String> {
+            hands
+                .iter()
+                .map(|hand| hand.cards.iter().map(Card::label).collect())
+                .collect()
+        };
+        assert_eq!(
+            labels(ranked::<Standard>(&cards.hands)),
+            ["32T3K", "KTJJT", "KK677", "T55J5", "QQQJA"]
+        );
+        assert_eq!(
+            labels(ranked::<Jokers>(&cards.hands)),
+            ["32T3K", "KK677", "T55J5", "QQQJA", "KTJJT"]
+        );
+    }
+
+    #[test]
+    fn malformed_hands_are_located() {
+        let error = |line| Hand::parse(line, 3).unwrap_err().to_string();
+        assert_eq!(
+            error("32X3K 765"),
+            "3:3: Expected one of \"23456789TJQKA\", found \"X\""
+        );
+        assert_eq!(error("32T3KK 765"), "3:6: Expected only five cards");
+        assert_eq!(error("32T3K bid"), "3:7: Expected a bid, found \"bid\"");
+        assert_eq!(
+            error("32T3 765"),
+            "3:5: Expected one of \"23456789TJQKA\", found a space"
+        );
+    }
+
+    #[test]
+    fn too_much_winnings_have_no_solution() {
+        let cards = CamelCards::parse("32T3K 1\nKK677 9223372036854775808\n").unwrap();
+        assert_eq!(
+            cards.part_1(),
+            Err(NoSolution(TOO_MUCH_WINNINGS.to_owned()))
+        );
+        let cards = CamelCards::parse("KK677 18446744073709551615\n32T3K 1\n").unwrap();
+        assert_eq!(
+            cards.part_2(),
+            Err(NoSolution(TOO_MUCH_WINNINGS.to_owned()))
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Benchmarks for the precomputation and the walking of both walkers.
+//!
+//! Run them with `cargo bench`. To compare the map implementations behind `MyMap`, save
+//! a baseline with the default FNV map and compare the alternatives against it:
+//!
+//! ```sh
+//! cargo bench -- --save-baseline fnv
+//! cargo bench --features std_hash_map -- --baseline fnv
+//! cargo bench --features btree_map -- --baseline fnv
+//! ```
+
+use std::hint::black_box;
+
+use advent_of_code_23_08::walker::{
+    bench_support::{power_walk_by_jump_map, walk_by_jump_map, JumpMapInput},
+    AOC8Solver, PowerWalker, Walker,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use fnv::FnvHashMap;
+
+static CHALLENGE: &str = include_str!("../../challenge.txt");
+
+/// The naive walk gives up after this many steps, it would take far too long otherwise
+const NAIVE_STEPS: usize = 1_000_000;
+
+/// Location names may use these, `A` and `Z` are reserved for starts and ends
+const ALPHABET: &[u8] = b"0123456789BCDEFGHIJKLMNOPQRSTUVWXY";
+
+struct Synthetic {
+    name: &'static str,
+    walk_instructions_len: usize,
+    /// Each ghost walks in a loop this many times as long as the walk instructions
+    loop_factors: &'static [usize],
+}
+
+const SIZES: [Synthetic; 3] = [
+    Synthetic {
+        name: "small",
+        walk_instructions_len: 5,
+        loop_factors: &[2, 3, 5],
+    },
+    Synthetic {
+        name: "medium",
+        walk_instructions_len: 11,
+        loop_factors: &[3, 5, 7, 11],
+    },
+    Synthetic {
+        name: "large",
+        walk_instructions_len: 31,
+        loop_factors: &[5, 7, 11, 13, 17, 19],
+    },
+];
+
+fn location_name(index: usize) -> String {
+    let base = ALPHABET.len();
+    [index / base / base, index / base % base, index % base]
+        .iter()
+        .map(|digit| ALPHABET[*digit] as char)
+        .collect()
+}
+
+impl Synthetic {
+    /// Builds a challenge shaped like the real one: every ghost runs in a loop of its own,
+    /// and reaches Z$ for the first time after exactly as many steps as it then takes to
+    /// get there again
+    fn challenge(&self) -> String {
+        let walk_instructions: String = "LR"
+            .chars()
+            .cycle()
+            .take(self.walk_instructions_len)
+            .collect();
+        let mut lines = vec![];
+        let mut next_index = 0;
+        for (ghost, factor) in self.loop_factors.iter().enumerate() {
+            let prefix = &location_name(ghost)[1..];
+            let start = format!("{prefix}A");
+            let end = format!("{prefix}Z");
+            let loop_locations: Vec<String> = (1..factor * self.walk_instructions_len)
+                .map(|offset| location_name(next_index + offset))
+                .collect();
+            next_index += loop_locations.len();
+
+            let first = &loop_locations[0];
+            lines.push(format!("{start} = ({first}, {first})"));
+            lines.push(format!("{end} = ({first}, {first})"));
+            for (from, to) in loop_locations.iter().zip(loop_locations.iter().skip(1)) {
+                lines.push(format!("{from} = ({to}, {to})"));
+            }
+            let last = loop_locations.last().unwrap();
+            lines.push(format!("{last} = ({end}, {end})"));
+        }
+        format!("{walk_instructions}\n\n{}\n", lines.join("\n"))
+    }
+}
+
+fn inputs() -> Vec<(&'static str, String)> {
+    SIZES
+        .iter()
+        .map(|size| (size.name, size.challenge()))
+        .collect()
+}
+
+/// Moves all ghosts one step at a time, like the first attempt described in the article
+struct NaiveWalk<'a> {
+    walk_instructions: &'a str,
+    left_right_map: FnvHashMap<&'a str, (&'a str, &'a str)>,
+}
+
+impl<'a> NaiveWalk<'a> {
+    fn new(input: &'a str) -> Self {
+        let (walk_instructions, network) = input.trim().split_once("\n\n").unwrap();
+        Self {
+            walk_instructions,
+            left_right_map: network
+                .lines()
+                .map(|line| (&line[0..3], (&line[7..10], &line[12..15])))
+                .collect(),
+        }
+    }
+
+    fn walk(&self, steps: usize) -> Vec<&'a str> {
+        let mut currents: Vec<&str> = self
+            .left_right_map
+            .keys()
+            .filter(|key| key.ends_with('A'))
+            .copied()
+            .collect();
+        for lr in self.walk_instructions.chars().cycle().take(steps) {
+            if currents.iter().all(|current| current.ends_with('Z')) {
+                break;
+            }
+            for current in &mut currents {
+                let (left, right) = self.left_right_map[current];
+                *current = if lr == 'L' { left } else { right };
+            }
+        }
+        currents
+    }
+}
+
+fn precomputation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("new");
+    group.sample_size(10);
+    let mut inputs = inputs();
+    inputs.push(("challenge", CHALLENGE.to_owned()));
+    for (name, input) in &inputs {
+        group.bench_with_input(BenchmarkId::new("Walker", name), input, |b, input| {
+            b.iter(|| Walker::new(black_box(input)))
+        });
+        group.bench_with_input(BenchmarkId::new("PowerWalker", name), input, |b, input| {
+            b.iter(|| PowerWalker::new(black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+fn jump_map(c: &mut Criterion) {
+    let mut group = c.benchmark_group("create_jump_map");
+    group.sample_size(10);
+    let mut inputs = inputs();
+    inputs.push(("challenge", CHALLENGE.to_owned()));
+    for (name, input) in &inputs {
+        let jump_map_input = JumpMapInput::new(input);
+        group.bench_with_input(
+            BenchmarkId::new("Walker", name),
+            &jump_map_input,
+            |b, jump_map_input| b.iter(|| jump_map_input.create_walker_jump_map()),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("PowerWalker", name),
+            &jump_map_input,
+            |b, jump_map_input| b.iter(|| jump_map_input.create_power_walker_jump_map()),
+        );
+    }
+    group.finish();
+}
+
+/// Throughput is reported in steps, so the walks can be compared by steps per second
+fn walk(c: &mut Criterion) {
+    let mut group = c.benchmark_group("walk");
+    for (name, input) in &inputs() {
+        let walker = Walker::new(input);
+        let power_walker = PowerWalker::new(input);
+        let steps = walk_by_jump_map(&walker);
+
+        group.throughput(Throughput::Elements(steps as u64));
+        group.bench_with_input(BenchmarkId::new("Walker", name), &walker, |b, walker| {
+            b.iter(|| walk_by_jump_map(walker))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("PowerWalker", name),
+            &power_walker,
+            |b, power_walker| b.iter(|| power_walk_by_jump_map(power_walker)),
+        );
+
+        group.throughput(Throughput::Elements(NAIVE_STEPS.min(steps) as u64));
+        group.bench_with_input(
+            BenchmarkId::new("naive", name),
+            &NaiveWalk::new(input),
+            |b, naive_walk| b.iter(|| naive_walk.walk(NAIVE_STEPS)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, precomputation, jump_map, walk);
+criterion_main!(benches);
@@ -0,0 +1,261 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    fs,
+    hash::Hasher,
+    io,
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use fnv::FnvHasher;
+use serde::{Deserialize, Serialize};
+
+use crate::progress::{Checkpoint, Progress, Strategy, WalkObserver};
+
+/**
+ * A checkpoint as it is written to disk, with everything needed to tell whether it
+ * may be resumed
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedCheckpoint {
+    pub input_hash: String,
+    pub strategy: Strategy,
+    #[serde(flatten)]
+    pub checkpoint: Checkpoint,
+}
+
+/**
+ * Stable across runs and Rust versions, unlike the std hasher
+ */
+pub fn hash_input(input: &str) -> String {
+    let mut hasher = FnvHasher::default();
+    hasher.write(input.as_bytes());
+    format!("{:016x}", hasher.finish())
+}
+
+#[derive(Debug)]
+pub enum CheckpointError {
+    Io(io::Error),
+    Format(serde_json::Error),
+    InputChanged,
+    StrategyChanged { was: Strategy, current: Strategy },
+}
+
+impl Display for CheckpointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckpointError::Io(error) => write!(f, "Could not access the checkpoint: {error}"),
+            CheckpointError::Format(error) => write!(f, "The checkpoint is corrupt: {error}"),
+            CheckpointError::InputChanged => write!(
+                f,
+                "The checkpoint was created for a different input, refusing to resume"
+            ),
+            CheckpointError::StrategyChanged { was, current } => write!(
+                f,
+                "The checkpoint was created with strategy {was:?}, but we are walking with {current:?}"
+            ),
+        }
+    }
+}
+
+impl Error for CheckpointError {}
+
+impl From<io::Error> for CheckpointError {
+    fn from(error: io::Error) -> Self {
+        CheckpointError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for CheckpointError {
+    fn from(error: serde_json::Error) -> Self {
+        CheckpointError::Format(error)
+    }
+}
+
+impl SavedCheckpoint {
+    /**
+     * Writes to a temporary file first, so that being killed halfway through
+     * never leaves a broken checkpoint behind
+     */
+    pub fn save(&self, path: &Path) -> Result<(), CheckpointError> {
+        let temporary = path.with_extension("tmp");
+        fs::write(&temporary, serde_json::to_string_pretty(self)?)?;
+        fs::rename(temporary, path)?;
+        Ok(())
+    }
+
+    /**
+     * Loads a checkpoint, refusing it if it does not belong to `input`, or was created
+     * walking with another strategy than `strategy`
+     */
+    pub fn load(path: &Path, input: &str, strategy: Strategy) -> Result<Self, CheckpointError> {
+        let saved: SavedCheckpoint = serde_json::from_str(&fs::read_to_string(path)?)?;
+        if saved.input_hash != hash_input(input) {
+            return Err(CheckpointError::InputChanged);
+        }
+        if saved.strategy != strategy {
+            return Err(CheckpointError::StrategyChanged {
+                was: saved.strategy,
+                current: strategy,
+            });
+        }
+        Ok(saved)
+    }
+}
+
+/**
+ * Saves a checkpoint every so often while walking, and cancels the walk once `stop` is
+ * set. The cancelled walk hands back where it stopped, which is for the caller to `save`,
+ * so that it is only written once
+ */
+pub struct CheckpointWriter<'a> {
+    path: PathBuf,
+    input_hash: String,
+    strategy: Strategy,
+    report_every: usize,
+    save_every: Duration,
+    last_save: Instant,
+    stop: &'a AtomicBool,
+}
+
+impl<'a> CheckpointWriter<'a> {
+    pub fn new(
+        path: PathBuf,
+        input: &str,
+        strategy: Strategy,
+        report_every: usize,
+        save_every: Duration,
+        stop: &'a AtomicBool,
+    ) -> Self {
+        Self {
+            path,
+            input_hash: hash_input(input),
+            strategy,
+            report_every,
+            save_every,
+            last_save: Instant::now(),
+            stop,
+        }
+    }
+
+    pub fn save(&mut self, checkpoint: Checkpoint) -> Result<(), CheckpointError> {
+        self.last_save = Instant::now();
+        SavedCheckpoint {
+            input_hash: self.input_hash.clone(),
+            strategy: self.strategy,
+            checkpoint,
+        }
+        .save(&self.path)
+    }
+}
+
+impl WalkObserver for CheckpointWriter<'_> {
+    fn report_every(&self) -> usize {
+        self.report_every
+    }
+
+    fn on_progress(&mut self, progress: &Progress) -> ControlFlow<()> {
+        if self.should_stop() {
+            return ControlFlow::Break(());
+        }
+        if self.last_save.elapsed() >= self.save_every {
+            let checkpoint = Checkpoint {
+                steps: progress.steps,
+                positions: progress.positions.iter().map(|p| p.to_string()).collect(),
+            };
+            if let Err(error) = self.save(checkpoint) {
+                eprintln!("{error}");
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn should_stop(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn saved() -> SavedCheckpoint {
+        SavedCheckpoint {
+            input_hash: hash_input("LR"),
+            strategy: Strategy::JumpMap,
+            checkpoint: Checkpoint {
+                steps: 4,
+                positions: vec!["11Z".to_owned(), "22B".to_owned()],
+            },
+        }
+    }
+
+    #[test]
+    fn saved_checkpoint_can_be_loaded() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("checkpoint.json");
+        saved().save(&path).unwrap();
+        assert_eq!(
+            SavedCheckpoint::load(&path, "LR", Strategy::JumpMap).unwrap(),
+            saved()
+        );
+    }
+
+    #[test]
+    fn checkpoint_for_other_input_is_refused() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("checkpoint.json");
+        saved().save(&path).unwrap();
+        assert!(matches!(
+            SavedCheckpoint::load(&path, "RL", Strategy::JumpMap),
+            Err(CheckpointError::InputChanged)
+        ));
+    }
+
+    #[test]
+    fn checkpoint_for_other_strategy_is_refused() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("checkpoint.json");
+        saved().save(&path).unwrap();
+        assert!(matches!(
+            SavedCheckpoint::load(&path, "LR", Strategy::JumpMapAssumingConstantZDistances),
+            Err(CheckpointError::StrategyChanged { .. })
+        ));
+    }
+
+    #[test]
+    fn writer_leaves_the_last_save_to_the_caller() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("checkpoint.json");
+        let stop = AtomicBool::new(false);
+        let mut writer = CheckpointWriter::new(
+            path.clone(),
+            "LR",
+            Strategy::JumpMap,
+            1,
+            Duration::ZERO,
+            &stop,
+        );
+        let progress = Progress {
+            steps: 4,
+            steps_per_second: 1.0,
+            positions: vec!["11Z", "22B"],
+        };
+
+        assert!(!writer.should_stop());
+        assert_eq!(writer.on_progress(&progress), ControlFlow::Continue(()));
+        assert_eq!(
+            SavedCheckpoint::load(&path, "LR", Strategy::JumpMap).unwrap(),
+            saved()
+        );
+        fs::remove_file(&path).unwrap();
+
+        stop.store(true, Ordering::Relaxed);
+        assert!(writer.should_stop());
+        assert_eq!(writer.on_progress(&progress), ControlFlow::Break(()));
+        assert!(!path.exists());
+    }
+}
@@ -1 +1,13 @@
+use aoc_core::{Day, Registry};
+use walker::{HandleableWalker, PowerWalker};
+
+pub mod checkpoint;
+pub mod progress;
 mod tapif;
+pub mod timing;
+pub mod walker;
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::of::<PowerWalker>());
+    registry.register_backend(Day::borrowing::<HandleableWalker>());
+}
@@ -1,44 +1,619 @@
-use std::{env, fs};
+use std::{
+    fmt::Display,
+    fs,
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
 
-mod walker;
-mod tapif;
+use advent_of_code_23_08::{
+    checkpoint::{CheckpointError, CheckpointWriter, SavedCheckpoint},
+    progress::{InvalidCheckpoint, Progress, Strategy, WalkObserver, WalkOutcome},
+    timing::Timings,
+    walker::{AOC8Solver, AOCTracer, PowerWalker, Walker, ZArrival},
+};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use serde_json::json;
+use tracing::info;
+use tracing_subscriber::{
+    fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer,
+};
 
-use walker::{AOC8Solver, AOCTracer, PowerWalker, Walker};
+/// Set by Ctrl-C, to stop walking after saving a last checkpoint
+static STOP: AtomicBool = AtomicBool::new(false);
 
-use crate::walker::get_walker;
+/// Solve and inspect Advent of Code 2023 day 08 challenges
+#[derive(Parser)]
+#[command(
+    version,
+    about,
+    after_help = "Exit codes:
+  0  Success
+  1  The challenge or checkpoint could not be read, or the challenge has no solution
+  2  Invalid arguments
+  3  The walk stopped before it finished, because of --max-steps or Ctrl-C"
+)]
+struct Cli {
+    /// Log more: -v for phases, -vv for progress, -vvv for everything. RUST_LOG takes precedence
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
 
-fn main() {
-    let file_name: String = env::args().nth(1).expect(
-        "You must pass the filename of the challenge input as first argument to this program",
-    );
+    /// Print how long each phase took as JSON to stderr when done
+    #[arg(long, global = true)]
+    timings: bool,
 
-    println!("Reading {file_name}");
+    #[command(subcommand)]
+    command: Command,
+}
 
-    let challenge =
-        fs::read_to_string(file_name).expect("Could not read the file you told me to analyze");
+#[derive(Subcommand)]
+enum Command {
+    /// Solve part 1, part 2, or both
+    Solve {
+        /// The challenge input
+        challenge: PathBuf,
 
-    let walker_wrapper = get_walker(challenge);
-    let walker = 
-        walker_wrapper.get();
-        // Walker::new(&challenge);
-        //PowerWalker::new(&challenge);
+        #[arg(long, value_enum, default_value_t = Part::Both)]
+        part: Part,
 
-    /* let result_part_1 = walker.solve_part_1();
-    //let result_part_2 = walker.solve_part_2();
-    println!("Part 1's result is {result_part_1}");
-    //println!("Part 2's result is {result_part_2}");
-    
-    let all_starts = walker.start_positions.clone();
-    let traced_locations = all_starts.iter().map(|location| {
-        (location.to_owned(), walker.get_all_locations_traversed_by(location.to_string(), 1_000_000, true).expect(&format!("Could not trace {}", location)))
-    });
+        #[command(flatten)]
+        solver: SolverArgs,
+
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// Give up walking for part 2 after about this many steps
+        #[arg(long)]
+        max_steps: Option<usize>,
+
+        /// Save the walk for part 2 to this file every minute, and when stopped
+        #[arg(long, conflicts_with = "resume")]
+        checkpoint: Option<PathBuf>,
+
+        /// Continue walking for part 2 from this checkpoint, and keep saving to it
+        #[arg(long)]
+        resume: Option<PathBuf>,
+    },
+    /// List the locations each ghost visits, until every location has been visited
+    Trace {
+        /// The challenge input
+        challenge: PathBuf,
+
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Print where all ghosts are, step by step
+    Steps {
+        /// The challenge input
+        challenge: PathBuf,
+
+        /// Print at most this many steps
+        #[arg(long, default_value_t = 50_000)]
+        limit: usize,
+
+        /// Keep going once every location has been visited
+        #[arg(long)]
+        keep_going: bool,
+    },
+    /// Show when each ghost is at Z$, and whether the lcm strategy applies
+    Analyze {
+        /// The challenge input
+        challenge: PathBuf,
+
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Export the network of locations
+    Export {
+        /// The challenge input
+        challenge: PathBuf,
+
+        #[arg(long, value_enum, default_value_t = ExportFormat::Dot)]
+        format: ExportFormat,
+    },
+    /// Measure how long creating the solver and solving take
+    Bench {
+        /// The challenge input
+        challenge: PathBuf,
+
+        #[command(flatten)]
+        solver: SolverArgs,
+
+        /// How often to solve
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+}
+
+#[derive(Args)]
+struct SolverArgs {
+    #[arg(long, value_enum, default_value_t = Backend::PowerWalker)]
+    backend: Backend,
+
+    /// How to solve part 2 [default: the one this binary was compiled for]
+    #[arg(long, value_enum)]
+    strategy: Option<StrategyArg>,
+}
+
+impl SolverArgs {
+    fn strategy(&self) -> Strategy {
+        self.strategy.map_or_else(Strategy::current, Strategy::from)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    Both,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Backend {
+    Walker,
+    PowerWalker,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum StrategyArg {
+    JumpMap,
+    ConstantZDistances,
+    Lcm,
+}
+
+impl From<StrategyArg> for Strategy {
+    fn from(strategy: StrategyArg) -> Self {
+        match strategy {
+            StrategyArg::JumpMap => Strategy::JumpMap,
+            StrategyArg::ConstantZDistances => Strategy::JumpMapAssumingConstantZDistances,
+            StrategyArg::Lcm => Strategy::Lcm,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    /// Graphviz
+    Dot,
+    Json,
+}
+
+enum Failure {
+    Read(PathBuf, std::io::Error),
+    Checkpoint(CheckpointError),
+    InvalidCheckpoint(InvalidCheckpoint),
+    NoWayFromAaaToZzz,
+    Stopped,
+}
 
-    for trace in traced_locations.clone() {
-        println!("{} traced {} locations: {:?}", trace.0, trace.1.len(), trace.1);
+impl Failure {
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            Failure::Stopped => ExitCode::from(3),
+            _ => ExitCode::FAILURE,
+        }
     }
-    println!("\n That's {} locations in total", traced_locations.map(|el| el.1.len()).sum::<usize>()); */
+}
 
-    (0..50000).into_iter().zip(walker.iter_steps(true)).for_each(|(_, step)| {
-        println!("{}", step);
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Read(path, error) => write!(f, "Could not read {}: {error}", path.display()),
+            Failure::Checkpoint(error) => write!(f, "{error}"),
+            Failure::InvalidCheckpoint(error) => write!(f, "{error}"),
+            Failure::NoWayFromAaaToZzz => write!(f, "There is no way from AAA to ZZZ"),
+            Failure::Stopped => write!(f, "Stopped before the ghosts met"),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let timings = init_tracing(cli.verbose, cli.timings);
+
+    let result = run(cli.command);
+
+    if let Some(timings) = timings {
+        eprintln!("{}", timings.to_json());
+    }
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            eprintln!("{failure}");
+            failure.exit_code()
+        }
+    }
+}
+
+/**
+ * Logs to stderr. Spans are timed regardless of the verbosity. With `timings`, returns
+ * the timings, to be printed as JSON once we are done
+ */
+fn init_tracing(verbose: u8, timings: bool) -> Option<Timings> {
+    let level = match verbose {
+        0 => "warn",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    };
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level));
+    let timings = timings.then(Timings::default);
+
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_span_events(FmtSpan::CLOSE)
+                .with_filter(filter),
+        )
+        .with(timings.as_ref().map(Timings::layer))
+        .init();
+    timings
+}
+
+fn read_challenge(path: &Path) -> Result<String, Failure> {
+    info!(path = %path.display(), "Reading challenge");
+    fs::read_to_string(path).map_err(|error| Failure::Read(path.to_owned(), error))
+}
+
+fn run(command: Command) -> Result<(), Failure> {
+    match command {
+        Command::Solve {
+            challenge,
+            part,
+            solver,
+            format,
+            max_steps,
+            checkpoint,
+            resume,
+        } => {
+            let input = read_challenge(&challenge)?;
+            let options = SolveOptions {
+                part,
+                strategy: solver.strategy(),
+                max_steps,
+                checkpoint: checkpoint.or(resume.clone()),
+                resume: resume.is_some(),
+            };
+            let answers = match solver.backend {
+                Backend::Walker => solve(&Walker::new(&input), &input, &options),
+                Backend::PowerWalker => solve(&PowerWalker::new(&input), &input, &options),
+            }?;
+            print_answers(&answers, format);
+            Ok(())
+        }
+        Command::Trace { challenge, format } => {
+            let input = read_challenge(&challenge)?;
+            let walker = Walker::new(&input);
+            let mut traces: Vec<_> = walker.build_traces().into_iter().collect();
+            traces.sort_by_key(|(start, _)| *start);
+            match format {
+                Format::Text => {
+                    for (start, trace) in traces {
+                        println!("{start} traced {} locations:", trace.len());
+                        for item in trace {
+                            println!("  {item}");
+                        }
+                    }
+                }
+                Format::Json => {
+                    let traces: serde_json::Map<_, _> = traces
+                        .into_iter()
+                        .map(|(start, trace)| (start.to_owned(), json!(trace)))
+                        .collect();
+                    println!("{}", serde_json::to_string_pretty(&traces).unwrap());
+                }
+            }
+            Ok(())
+        }
+        Command::Steps {
+            challenge,
+            limit,
+            keep_going,
+        } => {
+            let input = read_challenge(&challenge)?;
+            let walker = Walker::new(&input);
+            for step in walker.iter_steps(!keep_going).take(limit) {
+                println!("{step}");
+            }
+            Ok(())
+        }
+        Command::Analyze { challenge, format } => {
+            let input = read_challenge(&challenge)?;
+            let analysis = Walker::new(&input).analyze();
+            match format {
+                Format::Text => {
+                    println!(
+                        "{} locations, {} walk instructions",
+                        analysis.locations, analysis.walk_instructions
+                    );
+                    for ghost in &analysis.ghosts {
+                        let arrival = |z: &Option<_>| match z {
+                            Some(ZArrival { steps, location }) => {
+                                format!("{location} after {steps}")
+                            }
+                            None => "never".to_owned(),
+                        };
+                        println!(
+                            "{}: first at {}, then at {}{}",
+                            ghost.start,
+                            arrival(&ghost.first_z),
+                            arrival(&ghost.second_z),
+                            if ghost.periodic { ", periodic" } else { "" }
+                        );
+                    }
+                    println!(
+                        "The lcm strategy {}",
+                        if analysis.lcm_applies() {
+                            "applies"
+                        } else {
+                            "does not apply"
+                        }
+                    );
+                }
+                Format::Json => println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json!({
+                        "analysis": analysis,
+                        "lcm_applies": analysis.lcm_applies(),
+                    }))
+                    .unwrap()
+                ),
+            }
+            Ok(())
+        }
+        Command::Export { challenge, format } => {
+            let input = read_challenge(&challenge)?;
+            let walker = Walker::new(&input);
+            match format {
+                ExportFormat::Dot => print!("{}", walker.to_dot()),
+                ExportFormat::Json => println!(
+                    "{}",
+                    serde_json::to_string_pretty(&walker.network()).unwrap()
+                ),
+            }
+            Ok(())
+        }
+        Command::Bench {
+            challenge,
+            solver,
+            runs,
+            format,
+        } => {
+            let input = read_challenge(&challenge)?;
+            let results = match solver.backend {
+                Backend::Walker => bench::<Walker>(&input, solver.strategy(), runs),
+                Backend::PowerWalker => bench::<PowerWalker>(&input, solver.strategy(), runs),
+            };
+            print_bench(&results, format);
+            Ok(())
+        }
+    }
+}
+
+struct SolveOptions {
+    part: Part,
+    strategy: Strategy,
+    max_steps: Option<usize>,
+    checkpoint: Option<PathBuf>,
+    resume: bool,
+}
+
+#[derive(Default)]
+struct Answers {
+    part_1: Option<usize>,
+    part_2: Option<usize>,
+}
+
+fn solve<'a>(
+    solver: &impl AOC8Solver<'a>,
+    input: &str,
+    options: &SolveOptions,
+) -> Result<Answers, Failure> {
+    let mut answers = Answers::default();
+    if options.part != Part::Two {
+        answers.part_1 = Some(
+            solver
+                .try_solve_part_1()
+                .ok_or(Failure::NoWayFromAaaToZzz)?,
+        );
+    }
+    if options.part != Part::One {
+        answers.part_2 = Some(walk(solver, input, options)?);
+    }
+    Ok(answers)
+}
+
+/**
+ * Walks for part 2, saving a checkpoint every minute and when interrupted by Ctrl-C,
+ * if asked to
+ */
+fn walk<'a>(
+    solver: &impl AOC8Solver<'a>,
+    input: &str,
+    options: &SolveOptions,
+) -> Result<usize, Failure> {
+    let writer = options.checkpoint.as_ref().map(|path| {
+        ctrlc::set_handler(|| STOP.store(true, Ordering::Relaxed))
+            .expect("Could not listen for Ctrl-C");
+        CheckpointWriter::new(
+            path.clone(),
+            input,
+            options.strategy,
+            100_000_000_000,
+            Duration::from_secs(60),
+            &STOP,
+        )
     });
+    let mut observer = SolveObserver {
+        writer,
+        max_steps: options.max_steps,
+    };
+
+    let outcome = match (&options.checkpoint, options.resume) {
+        (Some(path), true) => {
+            let saved = SavedCheckpoint::load(path, input, options.strategy)
+                .map_err(Failure::Checkpoint)?;
+            info!(steps = saved.checkpoint.steps, "Resuming");
+            solver
+                .resume_part_2(&saved.checkpoint, options.strategy, &mut observer)
+                .map_err(Failure::InvalidCheckpoint)?
+        }
+        _ => solver.solve_part_2_observed(options.strategy, &mut observer),
+    };
+
+    match outcome {
+        WalkOutcome::Finished(steps) => Ok(steps),
+        WalkOutcome::Cancelled(checkpoint) => {
+            if let (Some(writer), Some(path)) = (&mut observer.writer, &options.checkpoint) {
+                let steps = checkpoint.steps;
+                writer.save(checkpoint).map_err(Failure::Checkpoint)?;
+                eprintln!(
+                    "Stopped at step {steps}, continue with --resume {}",
+                    path.display()
+                );
+            }
+            Err(Failure::Stopped)
+        }
+    }
+}
+
+/**
+ * Logs progress, stops the walk after `max_steps`, and saves checkpoints if there is a writer
+ */
+struct SolveObserver<'a> {
+    writer: Option<CheckpointWriter<'a>>,
+    max_steps: Option<usize>,
+}
+
+impl WalkObserver for SolveObserver<'_> {
+    fn report_every(&self) -> usize {
+        let writer_every = self
+            .writer
+            .as_ref()
+            .map_or(usize::MAX, |writer| writer.report_every());
+        writer_every.min(self.max_steps.unwrap_or(usize::MAX))
+    }
+
+    fn on_progress(&mut self, progress: &Progress) -> ControlFlow<()> {
+        info!(
+            steps = progress.steps,
+            steps_per_second = progress.steps_per_second,
+            "Still walking"
+        );
+        if let Some(writer) = &mut self.writer {
+            writer.on_progress(progress)?;
+        }
+        match self.max_steps {
+            Some(max_steps) if progress.steps >= max_steps => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
+
+    fn should_stop(&self) -> bool {
+        self.writer
+            .as_ref()
+            .is_some_and(|writer| writer.should_stop())
+    }
+}
+
+fn print_answers(answers: &Answers, format: Format) {
+    match format {
+        Format::Text => {
+            if let Some(part_1) = answers.part_1 {
+                println!("Part 1's result is {part_1}");
+            }
+            if let Some(part_2) = answers.part_2 {
+                println!("Part 2's result is {part_2}");
+            }
+        }
+        Format::Json => println!(
+            "{}",
+            json!({ "part_1": answers.part_1, "part_2": answers.part_2 })
+        ),
+    }
+}
+
+/**
+ * Seconds each run took per phase
+ */
+struct BenchResults {
+    new: Vec<f64>,
+    part_1: Vec<f64>,
+    part_2: Vec<f64>,
+}
+
+fn bench<'a, S: AOC8Solver<'a>>(input: &'a str, strategy: Strategy, runs: u32) -> BenchResults {
+    let mut results = BenchResults {
+        new: vec![],
+        part_1: vec![],
+        part_2: vec![],
+    };
+    for run in 1..=runs {
+        info!(run, runs, "Benchmarking");
+        let start = Instant::now();
+        let solver = S::new(input);
+        results.new.push(start.elapsed().as_secs_f64());
+
+        let start = Instant::now();
+        solver.try_solve_part_1();
+        results.part_1.push(start.elapsed().as_secs_f64());
+
+        let start = Instant::now();
+        let mut observer = SolveObserver {
+            writer: None,
+            max_steps: None,
+        };
+        solver.solve_part_2_observed(strategy, &mut observer);
+        results.part_2.push(start.elapsed().as_secs_f64());
+    }
+    results
+}
+
+fn print_bench(results: &BenchResults, format: Format) {
+    let phases = [
+        ("new", &results.new),
+        ("part_1", &results.part_1),
+        ("part_2", &results.part_2),
+    ];
+    let summarize = |seconds: &Vec<f64>| {
+        let min = seconds.iter().copied().fold(f64::INFINITY, f64::min);
+        let mean = seconds.iter().sum::<f64>() / seconds.len() as f64;
+        (min, mean)
+    };
+    match format {
+        Format::Text => {
+            println!("{:<8} {:>12} {:>12}", "phase", "min [s]", "mean [s]");
+            for (name, seconds) in phases {
+                let (min, mean) = summarize(seconds);
+                println!("{name:<8} {min:>12.6} {mean:>12.6}");
+            }
+        }
+        Format::Json => {
+            let phases: serde_json::Map<_, _> = phases
+                .into_iter()
+                .map(|(name, seconds)| {
+                    let (min, mean) = summarize(seconds);
+                    (
+                        name.to_owned(),
+                        json!({ "runs": seconds, "min": min, "mean": mean }),
+                    )
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&phases).unwrap());
+        }
+    }
 }
@@ -0,0 +1,169 @@
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    ops::ControlFlow,
+};
+
+use serde::{Deserialize, Serialize};
+
+/**
+ * Snapshot of a walk in progress, handed to a `WalkObserver`
+ */
+#[derive(Debug)]
+pub struct Progress<'a> {
+    pub steps: usize,
+    /**
+     * Average speed since the previous report
+     */
+    pub steps_per_second: f64,
+    /**
+     * Where each ghost currently is, in the order of the start positions
+     */
+    pub positions: Vec<&'a str>,
+}
+
+/**
+ * Gets told about the progress of a long walk, and may cancel it
+ */
+pub trait WalkObserver {
+    /**
+     * Report roughly every this many steps. The walk jumps many steps at once,
+     * so reports will not be exactly this far apart
+     */
+    fn report_every(&self) -> usize;
+
+    /**
+     * Return `ControlFlow::Break` to cancel the walk
+     */
+    fn on_progress(&mut self, progress: &Progress) -> ControlFlow<()>;
+
+    /**
+     * Ask `should_stop` roughly every this many steps. This is meant to be far more often
+     * than progress is reported, so that stopping does not wait for the next report
+     */
+    fn stop_check_every(&self) -> usize {
+        STOP_CHECK_EVERY
+    }
+
+    /**
+     * Return `true` to cancel the walk without waiting for the next report. Asked often,
+     * so it must be cheap
+     */
+    fn should_stop(&self) -> bool {
+        false
+    }
+}
+
+/**
+ * Well below a second of walking on the real input
+ */
+pub const STOP_CHECK_EVERY: usize = 10_000_000;
+
+/**
+ * Turns a closure into a `WalkObserver`
+ */
+pub struct ProgressCallback<F: FnMut(&Progress) -> ControlFlow<()>> {
+    pub every: usize,
+    pub callback: F,
+}
+
+impl<F: FnMut(&Progress) -> ControlFlow<()>> WalkObserver for ProgressCallback<F> {
+    fn report_every(&self) -> usize {
+        self.every
+    }
+
+    fn on_progress(&mut self, progress: &Progress) -> ControlFlow<()> {
+        (self.callback)(progress)
+    }
+}
+
+/**
+ * How part 2 is solved. The features `assume_constant_z_distances` and `use_lcm` pick
+ * the default
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Strategy {
+    /**
+     * Jump as far as the farthest next Z$ of all ghosts, looking it up again after every jump
+     */
+    JumpMap,
+    /**
+     * Look up how far to jump only once, before the first jump. Resuming with another strategy
+     * than a checkpoint was created with could therefore skip the solution
+     */
+    JumpMapAssumingConstantZDistances,
+    /**
+     * Only correct if every ghost arrives at its Z$ in constant intervals, and the first
+     * arrival takes exactly as long as every subsequent one. Never reports progress
+     */
+    Lcm,
+}
+
+impl Strategy {
+    /**
+     * The strategy this binary was compiled to use by default
+     */
+    pub fn current() -> Self {
+        if cfg!(feature = "use_lcm") {
+            Strategy::Lcm
+        } else if cfg!(feature = "assume_constant_z_distances") {
+            Strategy::JumpMapAssumingConstantZDistances
+        } else {
+            Strategy::JumpMap
+        }
+    }
+}
+
+/**
+ * Where a cancelled walk stopped. Pass it back to the solver to pick up from there
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub steps: usize,
+    pub positions: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum WalkOutcome {
+    Finished(usize),
+    Cancelled(Checkpoint),
+}
+
+#[derive(Debug)]
+pub enum InvalidCheckpoint {
+    UnknownLocation(String),
+    WrongNumberOfGhosts {
+        was: usize,
+        expected: usize,
+    },
+    /**
+     * Walks only ever stop after full passes over the walk instructions
+     */
+    NotAtStartOfInstructions {
+        steps: usize,
+        instructions: usize,
+    },
+}
+
+impl Display for InvalidCheckpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidCheckpoint::UnknownLocation(location) => {
+                write!(f, "The checkpoint contains \"{location}\", which is not on the map")
+            }
+            InvalidCheckpoint::WrongNumberOfGhosts { was, expected } => write!(
+                f,
+                "The checkpoint contains {was} ghosts, but the map has {expected} start positions"
+            ),
+            InvalidCheckpoint::NotAtStartOfInstructions {
+                steps,
+                instructions,
+            } => write!(
+                f,
+                "The checkpoint is at step {steps}, which is not a multiple of the {instructions} walk instructions"
+            ),
+        }
+    }
+}
+
+impl Error for InvalidCheckpoint {}
@@ -9,7 +9,7 @@ use tap::{Conv, Pipe};
 // TODO: I think this can be removed
 /// Defines utilities to conditionally tap closures to values in a chainable fashion.
 pub trait TapIf {
-    fn tap_refborrow_if<B>(self: &Self, condition: bool, runner: impl FnOnce(&B)) -> &Self
+    fn tap_refborrow_if<B>(&self, condition: bool, runner: impl FnOnce(&B)) -> &Self
     where
         Self: Borrow<B>,
         B: ?Sized,
@@ -21,7 +21,7 @@ pub trait TapIf {
     }
 
     fn tap_refborrow_mut_if<B>(
-        self: &mut Self,
+        &mut self,
         condition: bool,
         modifier: impl FnOnce(&mut B),
     ) -> &mut Self
@@ -36,7 +36,7 @@ pub trait TapIf {
     }
 
     fn tap_refborrow_if_fulfills(
-        self: &Self,
+        &self,
         predicate: impl FnOnce(&Self) -> bool,
         runner: impl FnOnce(&Self),
     ) -> &Self {
@@ -44,7 +44,7 @@ pub trait TapIf {
     }
 
     fn tap_refborrow_mut_if_fulfills(
-        self: &mut Self,
+        &mut self,
         predicate: impl FnOnce(&Self) -> bool,
         modifier: impl FnOnce(&mut Self),
     ) -> &mut Self {
@@ -130,14 +130,14 @@ where
         }
         self
     }
-    
+
     fn pipe_if_fulfills(
         self,
         predicate: impl FnOnce(&Self) -> bool,
         modifier: impl FnOnce(Self) -> Self,
     ) -> Self {
         predicate(&self).pipe(|condition| self.pipe_if(condition, modifier))
-    } 
+    }
 
     fn tap_if(self, condition: bool, runner: impl FnOnce(&Self)) -> Self {
         if condition {
@@ -146,7 +146,6 @@ where
         self
     }
 
-
     fn tap_if_fulfills(
         self,
         predicate: impl FnOnce(&Self) -> bool,
@@ -233,7 +232,7 @@ pub trait TapIfCloned
 where
     Self: Sized + Clone,
 {
-    fn pipe_cloned_if(self: &Self, condition: bool, modifier: impl FnOnce(&Self) -> Self) -> Self {
+    fn pipe_cloned_if(&self, condition: bool, modifier: impl FnOnce(&Self) -> Self) -> Self {
         if condition {
             modifier(self)
         } else {
@@ -242,7 +241,7 @@ where
     }
 
     fn pipe_cloned_if_fulfills(
-        self: &Self,
+        &self,
         predicate: impl FnOnce(&Self) -> bool,
         modifier: impl FnOnce(&Self) -> Self,
     ) -> Self {
@@ -0,0 +1,185 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+/**
+ * How long a span was open, together with the fields recorded on it
+ */
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpanTiming {
+    pub name: &'static str,
+    pub parent: Option<&'static str>,
+    pub fields: Map<String, Value>,
+    pub seconds: f64,
+}
+
+/**
+ * Collects a `SpanTiming` for every span that is closed, in the order they are closed.
+ * Clones share the collected timings, so keep one around to read them after walking
+ */
+#[derive(Debug, Clone, Default)]
+pub struct Timings(Arc<Mutex<Vec<SpanTiming>>>);
+
+impl Timings {
+    pub fn layer(&self) -> TimingLayer {
+        TimingLayer {
+            timings: self.clone(),
+        }
+    }
+
+    pub fn summary(&self) -> Vec<SpanTiming> {
+        self.0.lock().unwrap().clone()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.summary()).expect("Timings are always serializable")
+    }
+}
+
+/**
+ * Feeds the `Timings` it was created from
+ */
+pub struct TimingLayer {
+    timings: Timings,
+}
+
+/**
+ * Kept in the span's extensions until the span is closed
+ */
+struct Opened {
+    at: Instant,
+    fields: Map<String, Value>,
+}
+
+struct FieldVisitor<'a>(&'a mut Map<String, Value>);
+
+impl FieldVisitor<'_> {
+    fn insert(&mut self, field: &Field, value: impl Into<Value>) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+}
+
+impl Visit for FieldVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value);
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value);
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value);
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value);
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.insert(field, format!("{value:?}"));
+    }
+}
+
+impl<S: Subscriber + for<'l> LookupSpan<'l>> Layer<S> for TimingLayer {
+    fn on_new_span(&self, attributes: &Attributes<'_>, id: &Id, context: Context<'_, S>) {
+        let Some(span) = context.span(id) else {
+            return;
+        };
+        let mut fields = Map::new();
+        attributes.record(&mut FieldVisitor(&mut fields));
+        span.extensions_mut().insert(Opened {
+            at: Instant::now(),
+            fields,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, context: Context<'_, S>) {
+        let Some(span) = context.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        if let Some(opened) = extensions.get_mut::<Opened>() {
+            values.record(&mut FieldVisitor(&mut opened.fields));
+        }
+    }
+
+    fn on_close(&self, id: Id, context: Context<'_, S>) {
+        let Some(span) = context.span(&id) else {
+            return;
+        };
+        let Some(opened) = span.extensions_mut().remove::<Opened>() else {
+            return;
+        };
+        self.timings.0.lock().unwrap().push(SpanTiming {
+            name: span.name(),
+            parent: span.parent().map(|parent| parent.name()),
+            fields: opened.fields,
+            seconds: opened.at.elapsed().as_secs_f64(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing::{field, info_span};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+    use crate::walker::{AOC8Solver, PowerWalker};
+
+    #[test]
+    fn spans_are_timed_with_their_fields() {
+        let timings = Timings::default();
+        let subscriber = tracing_subscriber::registry().with(timings.layer());
+        tracing::subscriber::with_default(subscriber, || {
+            let outer = info_span!("outer", late = field::Empty).entered();
+            info_span!("inner", nodes = 3).in_scope(|| {});
+            outer.record("late", "recorded");
+        });
+
+        let summary = timings.summary();
+        assert_eq!(
+            summary
+                .iter()
+                .map(|timing| (timing.name, timing.parent))
+                .collect::<Vec<_>>(),
+            vec![("inner", Some("outer")), ("outer", None)]
+        );
+        assert_eq!(summary[0].fields["nodes"], 3);
+        assert_eq!(summary[1].fields["late"], "recorded");
+    }
+
+    #[test]
+    fn solving_reports_all_phases() {
+        let timings = Timings::default();
+        let subscriber = tracing_subscriber::registry().with(timings.layer());
+        tracing::subscriber::with_default(subscriber, || {
+            PowerWalker::new(
+                "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\nXXX = (XXX, XXX)\n",
+            )
+            .solve_part_2()
+        });
+
+        let names: Vec<_> = timings.summary().iter().map(|timing| timing.name).collect();
+        for phase in ["parse", "itertools", "jump_map", "part_1", "new"] {
+            assert!(names.contains(&phase), "{phase} missing from {names:?}");
+        }
+        #[cfg(not(feature = "use_lcm"))]
+        assert!(names.contains(&"walk"));
+    }
+}
@@ -1,43 +1,91 @@
-use colored::{Color, ColoredString, Colorize};
-use core::panic;
-use fnv::{FnvBuildHasher, FnvHashMap, FnvHashSet};
+use aoc_core::{Answer, BorrowingSolver, NoSolution, ParseError, Solver};
+use colored::{ColoredString, Colorize};
+use fnv::{FnvHashMap, FnvHashSet};
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::Serialize;
 use std::{
-    collections::{hash_map::RandomState, HashMap},
+    collections::{hash_map::RandomState, BTreeMap, HashMap},
     error::Error,
     fmt::{Debug, Display},
-    hash::Hash, mem::ManuallyDrop,
+    hash::{BuildHasher, Hash},
+    mem::ManuallyDrop,
+    ops::ControlFlow,
+    sync::OnceLock,
+    time::Instant,
 };
-use tap::{Conv, Pipe, Tap, TapOptional};
+use tap::Conv;
+use tracing::{debug, field, info_span};
 
-use crate::tapif::TapIfSized;
+use crate::{
+    progress::{Checkpoint, InvalidCheckpoint, Progress, Strategy, WalkObserver, WalkOutcome},
+    tapif::TapIfSized,
+};
 
-macro_rules! debug {
-    ($($arg:tt)*) => {{
-        #[cfg(feature = "debug")]
-        println!($($arg)*);
-    }};
-}
+const TITLE: &str = "Haunted Wasteland";
+const EXTRA_PARTS: &[&str] = &["Part 2, assuming the least common multiple is the answer"];
 
 /**
- * Public interface of a solver for the Advent of Code 2023 day 08 challenge part two.
+ * What solvers for the Advent of Code 2023 day 08 challenge offer beyond `Solver`,
+ * to walk for part 2 with more control
  */
-pub trait AOC8Solver<'a> {
+pub trait AOC8Solver<'a>: Solver<'a> {
+    /**
+     * Create and prepare a solver, panicking if the input is malformed
+     */
+    fn new(input: &'a str) -> Self {
+        let mut solver = Self::parse(input)
+            .unwrap_or_else(|error| panic!("Could not parse the challenge: {error}"));
+        solver.prepare();
+        solver
+    }
+
     /**
-     * Create a solver
+     * Calculate the solution to part 1, `None` if there is no way from AAA to ZZZ
      */
-    fn new(walk_instructions: &'a str) -> Self;
+    fn try_solve_part_1(&self) -> Option<usize>;
 
     /**
      * Calculate the solution to part 1
      */
-    fn solve_part_1(self: &Self) -> usize;
+    fn solve_part_1(&self) -> usize {
+        self.try_solve_part_1().expect(NO_WAY_FROM_AAA_TO_ZZZ)
+    }
+
+    /**
+     * Calculate the solution to part 2. Only detects that there is none if a ghost never
+     * gets to a Z$ at all. Ghosts that never are at a Z$ at the same time walk forever
+     */
+    fn try_solve_part_2(&self, strategy: Strategy) -> Result<usize, NoSolution>;
 
     /**
-     * Calculate the solution to part 2
+     * Calculate the solution to part 2, with the strategy this binary was compiled for
      */
-    fn solve_part_2(self: &Self) -> usize;
+    fn solve_part_2(&self) -> usize {
+        self.try_solve_part_2(Strategy::current())
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /**
+     * Calculate the solution to part 2, reporting the progress of the walk to `observer`,
+     * which may also cancel it
+     */
+    fn solve_part_2_observed(
+        &self,
+        strategy: Strategy,
+        observer: &mut dyn WalkObserver,
+    ) -> WalkOutcome;
+
+    /**
+     * Continue walking from where a cancelled walk for part 2 stopped. Pass the same strategy
+     * the walk was started with
+     */
+    fn resume_part_2(
+        &self,
+        checkpoint: &Checkpoint,
+        strategy: Strategy,
+        observer: &mut dyn WalkObserver,
+    ) -> Result<WalkOutcome, InvalidCheckpoint>;
 }
 
 /**
@@ -46,63 +94,131 @@ pub trait AOC8Solver<'a> {
 impl<'a, T: AOC8Solver<'a>> AOC8Parser for T {}
 
 trait AOC8Parser {
-    fn parse_challenge(input: &str) -> (&str, MyMap<&str, LeftRight>) {
-        match input.trim().split("\n\n").collect::<Vec<&str>>()[0..2] {
-            [lr, raw_map] => (
-                lr,
-                raw_map
-                    .split("\n")
-                    .map(|line| {
-                        MAP_PARSER
-                            .captures(line)
-                            .unwrap()
-                            .iter()
-                            .skip(1)
-                            .map(|c| c.unwrap().as_str())
-                            .collect::<Vec<&str>>()
-                    })
-                    .filter(|matches| matches.len() >= 3)
-                    .map(|matches| {
-                        (
-                            matches[0],
-                            LeftRight {
-                                left: matches[1],
-                                right: matches[2],
-                            },
-                        )
-                    })
-                    .collect(),
-            ),
-            _ => {
-                panic!("Problem during parsing")
+    fn parse_challenge(input: &str) -> Result<(&str, MyMap<&str, LeftRight<'_>>), ParseError> {
+        let span = info_span!("parse", nodes = field::Empty).entered();
+        let (walk_instructions, raw_map) = input.split_once("\n\n").ok_or_else(|| {
+            ParseError::new("Expected the walk instructions, an empty line, and then the map")
+        })?;
+        let walk_instructions = walk_instructions.trim_end();
+        if walk_instructions.is_empty() {
+            return Err(ParseError::new("There are no walk instructions").at_line(1));
+        }
+        if let Some((column, invalid)) = walk_instructions
+            .char_indices()
+            .find(|(_, lr)| *lr != 'L' && *lr != 'R')
+        {
+            return Err(
+                ParseError::new(format!("\"{invalid}\" is neither \"L\" nor \"R\""))
+                    .at(1, column + 1),
+            );
+        }
+
+        let mut left_right_map = MyMap::default();
+        // The map starts after the walk instructions and the empty line
+        for (line_number, line) in (3..).zip(raw_map.lines()) {
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+            let [location, left, right] = MAP_PARSER
+                .captures(line)
+                .ok_or_else(|| {
+                    ParseError::new(format!("Expected \"AAA = (BBB, CCC)\", found \"{line}\""))
+                        .at_line(line_number)
+                })?
+                .extract()
+                .1;
+            left_right_map.insert(location, LeftRight { left, right });
+        }
+
+        for (location, left_right) in &left_right_map {
+            if let Some(unknown) = [left_right.left, left_right.right]
+                .into_iter()
+                .find(|next| !left_right_map.contains_key(next))
+            {
+                return Err(ParseError::new(format!(
+                    "{location} leads to {unknown}, which is not on the map"
+                )));
             }
         }
+        let parsed = (walk_instructions, left_right_map);
+        span.record("nodes", parsed.1.len());
+        Ok(parsed)
     }
 
+    /**
+     * Returns `None` if `to` can not be reached. There are only so many combinations of location
+     * and position within the walk instructions, so once we took more steps than that, we are
+     * running in circles
+     */
     fn walk_from_to(
         from: &str,
         to: &Regex,
         walk_instructions: &str,
         left_right_map: &MyMap<&str, LeftRight>,
         force_walk: &bool,
-    ) -> usize {
+    ) -> Option<usize> {
         let mut current = from;
         let mut steps = 0;
+        let give_up_after = left_right_map.len() * walk_instructions.len() + 1;
 
         while !to.is_match(current) || (*force_walk && steps == 0) {
+            if steps > give_up_after {
+                return None;
+            }
             let next_rl_instruction =
                 walk_instructions.as_bytes()[steps % walk_instructions.len()] as char;
             let next_lr = left_right_map.get(current).unwrap();
             current = if next_rl_instruction == 'L' {
-                &next_lr.left
+                next_lr.left
             } else {
-                &next_lr.right
+                next_lr.right
             };
             steps += 1;
         }
+        Some(steps)
+    }
+
+    fn walk_from_aaa_to_zzz(
+        walk_instructions: &str,
+        left_right_map: &MyMap<&str, LeftRight>,
+    ) -> Option<usize> {
+        let span = info_span!("part_1", steps = field::Empty).entered();
+        if !left_right_map.contains_key("AAA") {
+            return None;
+        }
+        let steps = Self::walk_from_to("AAA", &IS_ZZZ, walk_instructions, left_right_map, &false);
+        span.record("steps", steps);
         steps
     }
 
+    fn create_itertool(
+        from: &str,
+        walk_instructions: &str,
+        left_right_map: &MyMap<&str, LeftRight>,
+    ) -> Itertool {
+        let mut current = from;
+        let mut z_offsets = vec![];
+        for (offset, lr) in walk_instructions.chars().enumerate() {
+            if END_IN_Z.is_match(current) {
+                z_offsets.push(offset);
+            }
+            current = left_right_map.access(&current).access(lr);
+        }
+
+        Itertool {
+            end_in_z_after: Self::walk_from_to(
+                from,
+                &END_IN_Z,
+                walk_instructions,
+                left_right_map,
+                &false,
+            ),
+            next_z: Self::walk_from_to(from, &END_IN_Z, walk_instructions, left_right_map, &true),
+            z_offsets,
+        }
+    }
+
     fn get_all_locations_matching<'a>(
         matcher: &Regex,
         left_right_map: &MyMap<&'a str, LeftRight>,
@@ -110,7 +226,7 @@ trait AOC8Parser {
         left_right_map
             .keys()
             .filter(|key| matcher.is_match(key))
-            .map(|key| *key)
+            .copied()
             .collect()
     }
 }
@@ -123,50 +239,163 @@ trait AOC8Parser {
  * the common `Accessor` trait
  */
 trait AOC8Walker<K> {
-    fn get_walk_instructions_len(self: &Self) -> usize;
-    fn get_start_positions(self: &Self) -> &Vec<K>;
-    fn get_itertools(self: &Self) -> &dyn Accessor<&K, Itertool>;
-    fn get_jump_map(self: &Self) -> &dyn Accessor<&K, Vec<K>>;
+    fn get_walk_instructions_len(&self) -> usize;
+    fn get_start_positions(&self) -> &Vec<K>;
+    fn get_itertools(&self) -> &dyn Accessor<&K, Itertool>;
+    fn get_jump_map(&self) -> &dyn Accessor<&K, Vec<K>>;
+    fn get_location_name<'s>(&'s self, key: &'s K) -> &'s str;
+    fn find_location(&self, name: &str) -> Option<&K>;
+
+    fn walk_by_jump_map(&self, strategy: Strategy) -> usize {
+        let currents = self.get_start_positions().iter().collect();
+        match self.walk_by_jump_map_from(0, currents, strategy, &mut LogObserver) {
+            WalkOutcome::Finished(steps) => steps,
+            WalkOutcome::Cancelled(_) => unreachable!("The log observer never cancels"),
+        }
+    }
 
-    fn walk_by_jump_map(self: &Self) -> usize {
-        let mut steps = 0;
+    fn resume_walk_by_jump_map(
+        &self,
+        checkpoint: &Checkpoint,
+        strategy: Strategy,
+        observer: &mut dyn WalkObserver,
+    ) -> Result<WalkOutcome, InvalidCheckpoint> {
+        let walk_instructions_length = self.get_walk_instructions_len();
+        if !checkpoint.steps.is_multiple_of(walk_instructions_length) {
+            return Err(InvalidCheckpoint::NotAtStartOfInstructions {
+                steps: checkpoint.steps,
+                instructions: walk_instructions_length,
+            });
+        }
+        if checkpoint.positions.len() != self.get_start_positions().len() {
+            return Err(InvalidCheckpoint::WrongNumberOfGhosts {
+                was: checkpoint.positions.len(),
+                expected: self.get_start_positions().len(),
+            });
+        }
+        let currents = checkpoint
+            .positions
+            .iter()
+            .map(|name| {
+                self.find_location(name)
+                    .ok_or_else(|| InvalidCheckpoint::UnknownLocation(name.to_owned()))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(self.walk_by_jump_map_from(checkpoint.steps, currents, strategy, observer))
+    }
 
-        let mut last = 0;
-        let print_every = 50_000_000_000;
+    /**
+     * `Strategy::Lcm` walks like `Strategy::JumpMapAssumingConstantZDistances`, as it makes
+     * the same assumption
+     */
+    fn walk_by_jump_map_from<'s>(
+        &'s self,
+        steps: usize,
+        currents: Vec<&'s K>,
+        strategy: Strategy,
+        observer: &mut dyn WalkObserver,
+    ) -> WalkOutcome {
+        let span = info_span!(
+            "walk",
+            ?strategy,
+            from = steps,
+            ghosts = currents.len(),
+            steps = field::Empty,
+            cancelled = field::Empty,
+        )
+        .entered();
+        let outcome = self.jump_until_z(steps, currents, strategy, observer);
+        match &outcome {
+            WalkOutcome::Finished(steps) => span.record("steps", steps),
+            WalkOutcome::Cancelled(checkpoint) => span
+                .record("steps", checkpoint.steps)
+                .record("cancelled", true),
+        };
+        outcome
+    }
 
-        let walk_instructions_length = self.get_walk_instructions_len();
+    fn jump_until_z<'s>(
+        &'s self,
+        mut steps: usize,
+        mut currents: Vec<&'s K>,
+        strategy: Strategy,
+        observer: &mut dyn WalkObserver,
+    ) -> WalkOutcome {
+        let report_every = observer.report_every();
+        let stop_check_every = observer.stop_check_every();
+        let mut last_report = (steps, Instant::now());
+        let mut last_stop_check = steps;
+        let cancel = |steps, currents: &[&K]| {
+            WalkOutcome::Cancelled(Checkpoint {
+                steps,
+                positions: currents
+                    .iter()
+                    .map(|current| self.get_location_name(current).to_owned())
+                    .collect(),
+            })
+        };
 
-        let mut currents: Vec<&K> = self.get_start_positions().into_iter().collect();
+        let walk_instructions_length = self.get_walk_instructions_len();
 
-        #[cfg(feature = "assume_constant_z_distances")]
-        let full_length_jumps = self.max_z_distance(&currents) / walk_instructions_length;
+        let constant_full_length_jumps = (strategy != Strategy::JumpMap)
+            .then(|| self.max_z_distance(&currents) / walk_instructions_length);
 
         while !self.have_same_z_distance(&currents) {
-            // I think this is not ideal. I'd prefer to only mutate a variable
-            // defined outside the loop, but "attributes on expressions are experimental"
-            #[cfg(not(feature = "assume_constant_z_distances"))]
-            let full_length_jumps = self.max_z_distance(&currents) / walk_instructions_length;
+            let full_length_jumps = constant_full_length_jumps
+                .unwrap_or_else(|| self.max_z_distance(&currents) / walk_instructions_length);
+
+            let jumps = if full_length_jumps == 0 {
+                // Every ghost arrives at its next Z$ before the walk instructions run out,
+                // so we must not skip a full pass without looking at each step
+                match self.first_common_z_offset(&currents) {
+                    Some(offset) => return WalkOutcome::Finished(steps + offset),
+                    None => 1,
+                }
+            } else {
+                full_length_jumps
+            };
 
-            if full_length_jumps == 0 {
-                panic!("Stuck!")
-            }
+            steps += jumps * walk_instructions_length;
 
-            steps += full_length_jumps * walk_instructions_length;
+            for current in &mut currents {
+                *current = &self.get_jump_map().access(current)[jumps];
+            }
 
-            if steps - last >= print_every {
-                debug!("Now at {steps}");
-                last = steps;
+            if steps - last_report.0 >= report_every {
+                let progress = Progress {
+                    steps,
+                    steps_per_second: (steps - last_report.0) as f64
+                        / last_report.1.elapsed().as_secs_f64(),
+                    positions: currents
+                        .iter()
+                        .map(|current| self.get_location_name(current))
+                        .collect(),
+                };
+                if observer.on_progress(&progress).is_break() {
+                    return cancel(steps, &currents);
+                }
+                last_report = (steps, Instant::now());
             }
 
-            for current in &mut currents {
-                *current = &self.get_jump_map().access(current)[full_length_jumps];
+            if steps - last_stop_check >= stop_check_every {
+                if observer.should_stop() {
+                    return cancel(steps, &currents);
+                }
+                last_stop_check = steps;
             }
         }
 
-        steps + self.get_itertools().access(currents[0]).end_in_z_after
+        WalkOutcome::Finished(
+            steps
+                + self
+                    .get_itertools()
+                    .access(currents[0])
+                    .end_in_z_after
+                    .expect(NO_SOLUTION),
+        )
     }
 
-    fn have_same_z_distance(self: &Self, keys: &Vec<&K>) -> bool {
+    fn have_same_z_distance(&self, keys: &Vec<&K>) -> bool {
         let z_distance_0 = self.get_itertools().access(keys[0]).end_in_z_after;
         for key in keys {
             if self.get_itertools().access(*key).end_in_z_after != z_distance_0 {
@@ -176,45 +405,171 @@ trait AOC8Walker<K> {
         true
     }
 
-    fn max_z_distance(self: &Self, currents: &Vec<&K>) -> usize {
+    fn max_z_distance(&self, currents: &Vec<&K>) -> usize {
         currents
             .iter()
-            .map(|current| self.get_itertools().access(*current).next_z)
+            .map(|current| {
+                self.get_itertools()
+                    .access(*current)
+                    .next_z
+                    .expect(NO_SOLUTION)
+            })
             .max()
             .unwrap_or(0)
     }
 
-    #[cfg(not(feature = "use_lcm"))]
-    fn internal_solve_part_2(self: &Self) -> usize {
-        self.walk_by_jump_map()
+    fn first_common_z_offset(&self, keys: &Vec<&K>) -> Option<usize> {
+        let (first, others) = keys.split_first()?;
+        self.get_itertools()
+            .access(first)
+            .z_offsets
+            .iter()
+            .find(|offset| {
+                others
+                    .iter()
+                    .all(|key| self.get_itertools().access(key).z_offsets.contains(offset))
+            })
+            .copied()
+    }
+
+    fn internal_solve_part_2(
+        &self,
+        strategy: Strategy,
+        observer: &mut dyn WalkObserver,
+    ) -> WalkOutcome {
+        match strategy {
+            Strategy::Lcm => WalkOutcome::Finished(self.solve_by_lcm()),
+            _ => {
+                let currents = self.get_start_positions().iter().collect();
+                self.walk_by_jump_map_from(0, currents, strategy, observer)
+            }
+        }
+    }
+
+    fn internal_try_solve_part_2(&self, strategy: Strategy) -> Result<usize, NoSolution>
+    where
+        K: PartialEq,
+    {
+        if self.get_start_positions().is_empty() {
+            return Err(NoSolution(NO_GHOSTS.to_owned()));
+        }
+        if self
+            .get_start_positions()
+            .iter()
+            .any(|start| self.get_itertools().access(start).end_in_z_after.is_none())
+        {
+            return Err(NoSolution(NO_SOLUTION.to_owned()));
+        }
+        if strategy == Strategy::Lcm
+            && !self
+                .get_start_positions()
+                .iter()
+                .all(|start| self.is_periodic(start))
+        {
+            return Err(NoSolution(NOT_PERIODIC.to_owned()));
+        }
+        match self.internal_solve_part_2(strategy, &mut LogObserver) {
+            WalkOutcome::Finished(steps) => Ok(steps),
+            WalkOutcome::Cancelled(_) => unreachable!("The log observer never cancels"),
+        }
+    }
+
+    fn internal_resume_part_2(
+        &self,
+        checkpoint: &Checkpoint,
+        strategy: Strategy,
+        observer: &mut dyn WalkObserver,
+    ) -> Result<WalkOutcome, InvalidCheckpoint> {
+        match strategy {
+            // Nothing to resume, the lcm is there right away
+            Strategy::Lcm => Ok(WalkOutcome::Finished(self.solve_by_lcm())),
+            _ => self.resume_walk_by_jump_map(checkpoint, strategy, observer),
+        }
+    }
+
+    /**
+     * Whether the ghost starting at `start` is at the same Z$ every time as many steps as to
+     * its first Z$ have passed, at the start of the walk instructions each time. The same as
+     * `GhostAnalysis::periodic`, but looked up in the jump map instead of walked step by step
+     */
+    fn is_periodic(&self, start: &K) -> bool
+    where
+        K: PartialEq,
+    {
+        let walk_instructions_length = self.get_walk_instructions_len();
+        let Some(first_z) = self.get_itertools().access(start).end_in_z_after else {
+            return false;
+        };
+        if first_z == 0 || !first_z.is_multiple_of(walk_instructions_length) {
+            return false;
+        }
+        let passes = first_z / walk_instructions_length;
+        let Some(z) = self.get_jump_map().access(start).get(passes) else {
+            return false;
+        };
+        self.get_itertools().access(z).next_z == Some(first_z)
+            && self.get_jump_map().access(z).get(passes) == Some(z)
     }
 
-    #[cfg(feature = "use_lcm")]
-    fn internal_solve_part_2(self: &Self) -> usize {
+    /**
+     * Only correct if every ghost arrives at its Z$ in constant intervals, and the first
+     * arrival takes exactly as long as every subsequent one. See `is_periodic`
+     */
+    fn solve_by_lcm(&self) -> usize {
         least_common_multiple(
             self.get_start_positions()
                 .iter()
-                .map(|start_pos| self.get_itertools().access(start_pos).end_in_z_after)
+                .map(|start_pos| {
+                    self.get_itertools()
+                        .access(start_pos)
+                        .end_in_z_after
+                        .expect(NO_SOLUTION)
+                })
                 .collect(),
         )
     }
 }
 
 /**
- * Faster map with "custom" hasher.
- * Replace with any map implementation if you wish.
+ * Logs progress as a `tracing` event, and never cancels
  */
-type MyMap<K, V> = HashMap<K, V, FnvBuildHasher>;
-// type MyMap<K, V> = BTreeMap<K, V>;
+struct LogObserver;
 
-#[derive(Debug)]
+impl WalkObserver for LogObserver {
+    fn report_every(&self) -> usize {
+        50_000_000_000
+    }
+
+    fn on_progress(&mut self, progress: &Progress) -> ControlFlow<()> {
+        debug!(
+            steps = progress.steps,
+            steps_per_second = progress.steps_per_second,
+            "Still walking"
+        );
+        ControlFlow::Continue(())
+    }
+}
+
+/**
+ * Faster map with "custom" hasher.
+ * Replace with any map implementation if you wish, or pick one of the alternatives
+ * via features to compare them in the benchmarks.
+ */
+#[cfg(not(any(feature = "std_hash_map", feature = "btree_map")))]
+type MyMap<K, V> = HashMap<K, V, fnv::FnvBuildHasher>;
+#[cfg(all(feature = "std_hash_map", not(feature = "btree_map")))]
+type MyMap<K, V> = HashMap<K, V, RandomState>;
+#[cfg(feature = "btree_map")]
+type MyMap<K, V> = std::collections::BTreeMap<K, V>;
+
+#[derive(Debug, Serialize)]
 pub struct LeftRight<'a> {
     left: &'a str,
     right: &'a str,
 }
 
 impl<'a> Accessor<char, &'a str> for LeftRight<'a> {
-    fn access(self: &Self, key: char) -> &&'a str {
+    fn access(&self, key: char) -> &&'a str {
         match key {
             'L' => &self.left,
             'R' => &self.right,
@@ -225,13 +580,42 @@ impl<'a> Accessor<char, &'a str> for LeftRight<'a> {
 
 pub struct Itertool {
     /**
-     * How many steps to take until we are at Z$. Can be 0
+     * How many steps to take until we are at Z$. Can be 0. `None` if there is no Z$ ahead
      */
-    end_in_z_after: usize,
+    end_in_z_after: Option<usize>,
     /**
      * How far to the _next_ Z$. Can not be 0
      */
-    next_z: usize,
+    next_z: Option<usize>,
+    /**
+     * After which steps of a single pass over the walk instructions we are at Z$
+     */
+    z_offsets: Vec<usize>,
+}
+
+const NO_SOLUTION: &str = "A ghost will never be at Z$ again, so there is no solution";
+const NO_WAY_FROM_AAA_TO_ZZZ: &str = "There is no way from AAA to ZZZ";
+const NO_GHOSTS: &str = "There are no ghosts, as no location ends in A";
+const NOT_PERIODIC: &str =
+    "Not every ghost arrives at the same Z$ in constant intervals, so the least common multiple is not the answer";
+
+/**
+ * The farthest distance from any location to its next Z$
+ */
+fn max_next_z<'a>(itertools: impl IntoIterator<Item = &'a Itertool>) -> usize {
+    itertools
+        .into_iter()
+        .filter_map(|itertool| itertool.next_z)
+        .max()
+        .unwrap_or(0)
+}
+
+/**
+ * How far the jump map must reach: as far as the most distant Z$, but at least one full pass
+ * over the walk instructions, so the walk can still advance when every Z$ is closer than that
+ */
+fn max_jumpable_distance(max_distance: usize, walk_instructions_len: usize) -> usize {
+    (max_distance - (max_distance % walk_instructions_len)).max(walk_instructions_len)
 }
 
 type ItertoolMap<'a> = MyMap<&'a str, Itertool>;
@@ -242,24 +626,54 @@ type JumpMap<'a> = MyMap<&'a str, Vec<&'a str>>;
  * be able to have a common walk implementation
  */
 trait Accessor<K, V> {
-    fn access(self: &Self, key: K) -> &V;
+    fn access(&self, key: K) -> &V;
 }
 
-impl<K, V> Accessor<&K, V> for MyMap<K, V>
+impl<K, V, S> Accessor<&K, V> for HashMap<K, V, S>
 where
     K: PartialEq + Eq + Hash,
+    S: BuildHasher,
 {
-    fn access(self: &Self, key: &K) -> &V {
+    fn access(&self, key: &K) -> &V {
         self.get(key).unwrap()
     }
 }
 
+#[cfg(feature = "btree_map")]
+impl<K, V> Accessor<&K, V> for std::collections::BTreeMap<K, V>
+where
+    K: Ord,
+{
+    fn access(&self, key: &K) -> &V {
+        self.get(key).unwrap()
+    }
+}
+
+fn part_1_answer<'a>(solver: &impl AOC8Solver<'a>) -> Result<Answer, NoSolution> {
+    solver
+        .try_solve_part_1()
+        .map(Answer::from)
+        .ok_or_else(|| NoSolution(NO_WAY_FROM_AAA_TO_ZZZ.to_owned()))
+}
+
+fn part_2_answer<'a>(
+    solver: &impl AOC8Solver<'a>,
+    strategy: Strategy,
+) -> Result<Answer, NoSolution> {
+    solver.try_solve_part_2(strategy).map(Answer::from)
+}
+
 pub struct Walker<'a> {
     walk_map: MyMap<&'a str, LeftRight<'a>>,
     walk_instructions: &'a str,
     walk_instructions_len: usize,
+    part_1: Option<usize>,
     itertools: MyMap<&'a str, Itertool>,
-    jump_map: JumpMap<'a>,
+    max_distance: usize,
+    /**
+     * Created by `prepare`, or when it is first needed
+     */
+    jump_map: OnceLock<JumpMap<'a>>,
     pub start_positions: Vec<&'a str>,
 }
 
@@ -325,10 +739,12 @@ impl SelfRef for HandleableWalker {
     type Container<'a> = Walker<'a>;
 }
 
+impl BorrowingSolver for HandleableWalker {
+    type Solver<'a> = Walker<'a>;
+}
+
 pub fn get_walker(input: String) -> SelfRefWrapper<HandleableWalker, String> {
-    SelfRefWrapper::new(input, |input| {
-        Walker::new(input)
-    })
+    SelfRefWrapper::new(input, |input| Walker::new(input))
 }
 
 /*
@@ -339,15 +755,16 @@ impl<'a> Walker<'a> {
     fn create_jump_map(
         left_right_map: &MyMap<&'a str, LeftRight<'a>>,
         walk_instructions: &str,
+        max_distance: usize,
     ) -> JumpMap<'a> {
-        debug!("Computing jump map");
         let all_keys = left_right_map.keys();
-        let all_distances_to_z = all_keys.clone().map(|key| {
-            Self::walk_from_to(&key, &END_IN_Z, walk_instructions, left_right_map, &false)
-        });
-        let max_distance = all_distances_to_z.max().unwrap();
-        let max_jumpable_distance = max_distance - (max_distance % walk_instructions.len());
-        debug!("Max jumpable distance: {max_jumpable_distance}");
+        let max_jumpable_distance = max_jumpable_distance(max_distance, walk_instructions.len());
+        let _span = info_span!(
+            "jump_map",
+            nodes = left_right_map.len(),
+            max_jumpable_distance
+        )
+        .entered();
         let mut jump_map = MyMap::default();
         jump_map.extend(all_keys.map(|key| {
             let mut jump_list: Vec<&str> = vec![key];
@@ -366,205 +783,332 @@ impl<'a> Walker<'a> {
             }
             (key.to_owned(), jump_list)
         }));
-        debug!("Computed jump map");
         jump_map
     }
+
+    fn jump_map(&self) -> &JumpMap<'a> {
+        self.jump_map.get_or_init(|| {
+            Self::create_jump_map(&self.walk_map, self.walk_instructions, self.max_distance)
+        })
+    }
 }
 
-impl<'a> AOC8Solver<'a> for Walker<'a> {
-    fn new(input: &'a str) -> Self {
+impl<'a> Solver<'a> for Walker<'a> {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = TITLE;
+    const EXTRA_PARTS: &'static [&'static str] = EXTRA_PARTS;
+
+    fn parse(input: &'a str) -> Result<Self, ParseError> {
         // (&'a str, HashMap<&'a str, LeftRight<'a>)
-        let (walk_instructions, left_right_map) = Self::parse_challenge(&input);
-        debug!("Creating new Walker");
+        let _span = info_span!("new", solver = "Walker").entered();
+        let (walk_instructions, left_right_map) = Self::parse_challenge(input)?;
         let mut itertools = ItertoolMap::default();
-        itertools.extend(left_right_map.keys().map(|key| {
-            let end_in_z_after =
-                Self::walk_from_to(key, &END_IN_Z, &walk_instructions, &left_right_map, &false);
-
-            let next_z =
-                Self::walk_from_to(key, &END_IN_Z, &walk_instructions, &left_right_map, &true);
-
-            (
-                *key,
-                Itertool {
-                    end_in_z_after,
-                    next_z,
-                },
-            )
-        }));
-        let jump_map = Self::create_jump_map(&left_right_map, &walk_instructions);
+        info_span!("itertools", nodes = left_right_map.len()).in_scope(|| {
+            itertools.extend(left_right_map.keys().map(|key| {
+                (
+                    *key,
+                    Self::create_itertool(key, walk_instructions, &left_right_map),
+                )
+            }))
+        });
+        let max_distance = max_next_z(itertools.values());
         let start_positions = Self::get_all_locations_matching(&END_IN_A, &left_right_map);
-        debug!("Created new Walker");
-        Self {
+        let part_1 = Self::walk_from_aaa_to_zzz(walk_instructions, &left_right_map);
+        Ok(Self {
             walk_map: left_right_map,
             walk_instructions,
             walk_instructions_len: walk_instructions.len(),
+            part_1,
             itertools,
             start_positions,
-            jump_map,
-        }
+            max_distance,
+            jump_map: OnceLock::new(),
+        })
+    }
+
+    fn prepare(&mut self) {
+        self.jump_map();
+    }
+
+    fn part_1(&self) -> Result<Answer, NoSolution> {
+        part_1_answer(self)
+    }
+
+    fn part_2(&self) -> Result<Answer, NoSolution> {
+        part_2_answer(self, Strategy::current())
+    }
+
+    fn extra_part(&self, _part: usize) -> Result<Answer, NoSolution> {
+        part_2_answer(self, Strategy::Lcm)
+    }
+}
+
+impl<'a> AOC8Solver<'a> for Walker<'a> {
+    fn try_solve_part_1(&self) -> Option<usize> {
+        self.part_1
     }
 
-    fn solve_part_1(self: &Self) -> usize {
-        self.itertools.access(&"AAA").end_in_z_after
+    fn try_solve_part_2(&self, strategy: Strategy) -> Result<usize, NoSolution> {
+        self.internal_try_solve_part_2(strategy)
     }
 
-    fn solve_part_2(self: &Self) -> usize {
-        self.internal_solve_part_2()
+    fn solve_part_2_observed(
+        &self,
+        strategy: Strategy,
+        observer: &mut dyn WalkObserver,
+    ) -> WalkOutcome {
+        self.internal_solve_part_2(strategy, observer)
+    }
+
+    fn resume_part_2(
+        &self,
+        checkpoint: &Checkpoint,
+        strategy: Strategy,
+        observer: &mut dyn WalkObserver,
+    ) -> Result<WalkOutcome, InvalidCheckpoint> {
+        self.internal_resume_part_2(checkpoint, strategy, observer)
     }
 }
 
 impl<'a> AOC8Walker<&'a str> for Walker<'a> {
-    fn get_walk_instructions_len(self: &Self) -> usize {
+    fn get_walk_instructions_len(&self) -> usize {
         self.walk_instructions_len
     }
 
     fn get_jump_map(self: &Walker<'a>) -> &dyn Accessor<&&'a str, Vec<&'a str>> {
-        &self.jump_map
+        self.jump_map()
     }
 
-    fn get_start_positions(self: &Self) -> &Vec<&'a str> {
+    fn get_start_positions(&self) -> &Vec<&'a str> {
         &self.start_positions
     }
 
-    fn get_itertools(self: &Self) -> &dyn Accessor<&&'a str, Itertool> {
+    fn get_itertools(&self) -> &dyn Accessor<&&'a str, Itertool> {
         &self.itertools
     }
+
+    fn get_location_name<'s>(&'s self, key: &'s &'a str) -> &'s str {
+        key
+    }
+
+    fn find_location(&self, name: &str) -> Option<&&'a str> {
+        self.walk_map.get_key_value(name).map(|(key, _)| key)
+    }
 }
 
 type PowerItertoolMap = Vec<Itertool>;
 type PowerJumpMap = Vec<Vec<usize>>;
 
 impl<V> Accessor<&usize, V> for Vec<V> {
-    fn access(self: &Self, key: &usize) -> &V {
+    fn access(&self, key: &usize) -> &V {
         &self[*key]
     }
 }
 
 pub struct PowerWalker {
-    start_index_part_1: usize,
+    part_1: Option<usize>,
     walk_instructions_len: usize,
     itertools: Vec<Itertool>,
     start_positions: Vec<usize>,
-    jump_map: PowerJumpMap,
+    walk_instructions: String,
+    /**
+     * Where to go from each location, to the left and to the right
+     */
+    successors: Vec<(usize, usize)>,
+    max_distance: usize,
+    /**
+     * Created by `prepare`, or when it is first needed
+     */
+    jump_map: OnceLock<PowerJumpMap>,
+    /**
+     * Only needed to report where the ghosts are
+     */
+    location_names: Vec<String>,
 }
 
 impl PowerWalker {
-    fn create_jump_map(
-        walk_instructions: &str,
+    fn index_locations<'a>(left_right_map: &MyMap<&'a str, LeftRight>) -> HashMap<&'a str, usize> {
+        HashMap::<&str, usize, RandomState>::from_iter(
+            left_right_map
+                .keys()
+                .enumerate()
+                .map(|(index, key)| (*key, index)),
+        )
+    }
+
+    fn index_successors(
         left_right_map: &MyMap<&str, LeftRight>,
         str_to_usize: &HashMap<&str, usize>,
+    ) -> Vec<(usize, usize)> {
+        left_right_map
+            .values()
+            .map(|left_right| {
+                (
+                    str_to_usize[left_right.left],
+                    str_to_usize[left_right.right],
+                )
+            })
+            .collect()
+    }
+
+    fn create_jump_map(
+        walk_instructions: &str,
+        successors: &[(usize, usize)],
+        max_distance: usize,
     ) -> PowerJumpMap {
-        let all_keys = left_right_map.keys();
-        let all_distances_to_z = all_keys.clone().map(|key| {
-            Self::walk_from_to(&key, &END_IN_Z, walk_instructions, left_right_map, &false)
-        });
-        let max_distance = all_distances_to_z.max().unwrap();
-        let max_jumpable_distance = max_distance - (max_distance % walk_instructions.len());
-        debug!("Max jumpable distance: {max_jumpable_distance}");
-        let jump_map = all_keys
-            .map(|key| {
-                let mut jump_list: Vec<usize> = vec![*str_to_usize.get(key).unwrap()];
-                let mut current = key;
+        let max_jumpable_distance = max_jumpable_distance(max_distance, walk_instructions.len());
+        let _span =
+            info_span!("jump_map", nodes = successors.len(), max_jumpable_distance).entered();
+        (0..successors.len())
+            .map(|location| {
+                let mut jump_list: Vec<usize> = vec![location];
+                let mut current = location;
                 for (i, lr) in (0..max_jumpable_distance).zip(walk_instructions.chars().cycle()) {
-                    let next_instruction = left_right_map.get(current).unwrap();
-                    let next = if lr == 'L' {
-                        &next_instruction.left
-                    } else {
-                        &next_instruction.right
-                    };
+                    let (left, right) = successors[current];
+                    current = if lr == 'L' { left } else { right };
                     if (i + 1) % walk_instructions.len() == 0 {
-                        jump_list.push(*str_to_usize.get(next).unwrap())
+                        jump_list.push(current)
                     };
-                    current = next;
                 }
                 jump_list
             })
-            .collect();
-        debug!("Computed jump map");
-        jump_map
+            .collect()
+    }
+
+    fn jump_map(&self) -> &PowerJumpMap {
+        self.jump_map.get_or_init(|| {
+            Self::create_jump_map(&self.walk_instructions, &self.successors, self.max_distance)
+        })
     }
 }
 
-impl<'a> AOC8Solver<'a> for PowerWalker {
-    fn new(input: &'a str) -> Self {
-        debug!("Creating new PowerWalker");
-        let (walk_instructions, left_right_map) = Self::parse_challenge(&input);
+impl<'a> Solver<'a> for PowerWalker {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = TITLE;
+    const EXTRA_PARTS: &'static [&'static str] = EXTRA_PARTS;
+
+    fn parse(input: &'a str) -> Result<Self, ParseError> {
+        let _span = info_span!("new", solver = "PowerWalker").entered();
+        let (walk_instructions, left_right_map) = Self::parse_challenge(input)?;
         let mut itertools = PowerItertoolMap::with_capacity(left_right_map.len());
 
-        let str_to_usize = HashMap::<&str, usize, RandomState>::from_iter(
-            left_right_map
-                .keys()
-                .enumerate()
-                .map(|(index, key)| (*key, index)),
-        );
+        let str_to_usize = Self::index_locations(&left_right_map);
 
-        itertools.extend(left_right_map.keys().map(|key| {
-            let end_in_z_after =
-                Self::walk_from_to(key, &END_IN_Z, &walk_instructions, &left_right_map, &false);
-            let next_z =
-                Self::walk_from_to(key, &END_IN_Z, &walk_instructions, &left_right_map, &true);
-            Itertool {
-                end_in_z_after,
-                next_z,
-            }
-        }));
+        info_span!("itertools", nodes = left_right_map.len()).in_scope(|| {
+            itertools.extend(
+                left_right_map
+                    .keys()
+                    .map(|key| Self::create_itertool(key, walk_instructions, &left_right_map)),
+            )
+        });
+        let max_distance = max_next_z(&itertools);
 
         let rust_temporary = Self::get_all_locations_matching(&END_IN_A, &left_right_map);
         let start_positions = rust_temporary
             .iter()
             .map(|x| str_to_usize.get(x).unwrap().to_owned())
             .collect();
-        let jump_map = Self::create_jump_map(&walk_instructions, &left_right_map, &str_to_usize);
-        let start_index_part_1 = *str_to_usize.get("AAA").unwrap();
+        let successors = Self::index_successors(&left_right_map, &str_to_usize);
+        let part_1 = Self::walk_from_aaa_to_zzz(walk_instructions, &left_right_map);
 
-        debug!("Created new PowerWalker");
-        Self {
-            start_index_part_1,
-            jump_map,
+        let location_names = left_right_map.keys().map(|key| key.to_string()).collect();
+
+        Ok(Self {
+            part_1,
+            location_names,
+            walk_instructions: walk_instructions.to_owned(),
             walk_instructions_len: walk_instructions.len(),
+            successors,
+            max_distance,
+            jump_map: OnceLock::new(),
             itertools,
             start_positions,
-        }
+        })
     }
 
-    fn solve_part_1(self: &Self) -> usize {
-        self.itertools
-            .access(&self.start_index_part_1)
-            .end_in_z_after
+    fn prepare(&mut self) {
+        self.jump_map();
     }
 
-    fn solve_part_2(self: &Self) -> usize {
-        self.internal_solve_part_2()
+    fn part_1(&self) -> Result<Answer, NoSolution> {
+        part_1_answer(self)
+    }
+
+    fn part_2(&self) -> Result<Answer, NoSolution> {
+        part_2_answer(self, Strategy::current())
+    }
+
+    fn extra_part(&self, _part: usize) -> Result<Answer, NoSolution> {
+        part_2_answer(self, Strategy::Lcm)
+    }
+}
+
+impl<'a> AOC8Solver<'a> for PowerWalker {
+    fn try_solve_part_1(&self) -> Option<usize> {
+        self.part_1
+    }
+
+    fn try_solve_part_2(&self, strategy: Strategy) -> Result<usize, NoSolution> {
+        self.internal_try_solve_part_2(strategy)
+    }
+
+    fn solve_part_2_observed(
+        &self,
+        strategy: Strategy,
+        observer: &mut dyn WalkObserver,
+    ) -> WalkOutcome {
+        self.internal_solve_part_2(strategy, observer)
+    }
+
+    fn resume_part_2(
+        &self,
+        checkpoint: &Checkpoint,
+        strategy: Strategy,
+        observer: &mut dyn WalkObserver,
+    ) -> Result<WalkOutcome, InvalidCheckpoint> {
+        self.internal_resume_part_2(checkpoint, strategy, observer)
     }
 }
 
 impl AOC8Walker<usize> for PowerWalker {
-    fn get_walk_instructions_len(self: &Self) -> usize {
+    fn get_walk_instructions_len(&self) -> usize {
         self.walk_instructions_len
     }
 
-    fn get_itertools(self: &Self) -> &dyn Accessor<&usize, Itertool> {
+    fn get_itertools(&self) -> &dyn Accessor<&usize, Itertool> {
         &self.itertools
     }
 
-    fn get_start_positions(self: &Self) -> &Vec<usize> {
+    fn get_start_positions(&self) -> &Vec<usize> {
         &self.start_positions
     }
 
-    fn get_jump_map(self: &Self) -> &dyn Accessor<&usize, Vec<usize>> {
-        &self.jump_map
+    fn get_jump_map(&self) -> &dyn Accessor<&usize, Vec<usize>> {
+        self.jump_map()
+    }
+
+    fn get_location_name<'s>(&'s self, key: &'s usize) -> &'s str {
+        &self.location_names[*key]
+    }
+
+    fn find_location(&self, name: &str) -> Option<&usize> {
+        let index = self
+            .location_names
+            .iter()
+            .position(|location| location == name)?;
+        // The first entry of each jump list is the location itself
+        Some(&self.jump_map()[index][0])
     }
 }
 
 // inspired by https://stackoverflow.com/questions/31302054/how-to-find-the-least-common-multiple-of-a-range-of-numbers
 pub fn least_common_multiple(numbers: Vec<usize>) -> usize {
     fn gcd(a: &usize, b: &usize) -> usize {
-        return if *b == 0 {
+        if *b == 0 {
             a.to_owned()
         } else {
             gcd(b, &(*a % *b))
-        };
+        }
     }
 
     fn lcm(a: &usize, b: &usize) -> usize {
@@ -579,20 +1123,17 @@ pub trait AOCTracer<T> {
      * Can be used to check hypothesis that individual routes are non-overlapping
      */
     fn get_all_locations_traversed_by(
-        self: &Self,
+        &self,
         start_point: T,
         steps: usize,
         include_start: bool,
     ) -> Result<Vec<T>, Box<dyn Error>>;
 
-    fn build_traces(self: &Self) -> FnvHashMap<&str, Vec<TraceItem>>;
+    fn build_traces(&self) -> FnvHashMap<&str, Vec<TraceItem>>;
 
     /// Returns an iterator over debug strings listing information about the current iteration
     /// for all strands
-    fn iter_steps(
-        self: &Self,
-        stop_at_repeat: bool,
-    ) -> impl Iterator<Item = String>;
+    fn iter_steps(&self, stop_at_repeat: bool) -> impl Iterator<Item = String>;
 }
 
 #[derive(Debug)]
@@ -620,25 +1161,26 @@ impl<T: Display> Display for InvalidArgumentChoice<T> {
 
 impl<T: Display + Debug> Error for InvalidArgumentChoice<T> {}
 
+#[derive(Serialize)]
 pub struct TraceItem {
     pub location: String,
     pub left: String,
     pub right: String,
 }
 
-impl ToString for TraceItem {
-    fn to_string(&self) -> String {
-        format!("{} = ({}, {})", self.location, self.left, self.right)
+impl Display for TraceItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = ({}, {})", self.location, self.left, self.right)
     }
 }
 
 impl<'a> AOCTracer<String> for Walker<'a> {
     fn get_all_locations_traversed_by(
-        self: &Self,
+        &self,
         start_point: String,
         steps: usize,
         include_start: bool,
-    ) -> Result<Vec<String>, Box<(dyn std::error::Error + 'static)>> {
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + 'static>> {
         self.start_positions
             .iter()
             .find(|val| (**val).eq(&start_point))
@@ -680,7 +1222,7 @@ impl<'a> AOCTracer<String> for Walker<'a> {
             .collect())
     }
 
-    fn build_traces(self: &Self) -> FnvHashMap<&str, Vec<TraceItem>> {
+    fn build_traces(&self) -> FnvHashMap<&str, Vec<TraceItem>> {
         let mut traced = FnvHashMap::default();
         let mut traces =
             FnvHashMap::from_iter(self.start_positions.iter().map(|pos| (*pos, vec![])));
@@ -688,7 +1230,7 @@ impl<'a> AOCTracer<String> for Walker<'a> {
             FnvHashMap::from_iter(self.start_positions.iter().map(|key| (*key, *key)));
         for lr in self.walk_instructions.chars().cycle() {
             for (start, current) in &mut currents {
-                let left_right = self.walk_map.access(&current);
+                let left_right = self.walk_map.access(current);
 
                 if *traced
                     .entry(*current)
@@ -713,7 +1255,7 @@ impl<'a> AOCTracer<String> for Walker<'a> {
         traces
     }
 
-    fn iter_steps(self: &Self, stop_at_repeat: bool) -> impl Iterator<Item = String> {
+    fn iter_steps(&self, stop_at_repeat: bool) -> impl Iterator<Item = String> {
         self.walk_instructions.chars().cycle().scan(
             (
                 0,
@@ -722,7 +1264,9 @@ impl<'a> AOCTracer<String> for Walker<'a> {
                 false,
             ),
             move |(i, currents, iter_count, exit_after_next), lr| {
-                if *exit_after_next { return None };
+                if *exit_after_next {
+                    return None;
+                };
                 if stop_at_repeat && iter_count.len() == self.walk_map.len() {
                     *exit_after_next = true;
                 }
@@ -730,13 +1274,7 @@ impl<'a> AOCTracer<String> for Walker<'a> {
                     "{:>4} > {:>2}: {lr}\t{}",
                     i,
                     *i / self.walk_instructions_len + 1,
-                    positions_to_string(
-                        &currents,
-                        &self.walk_map,
-                        iter_count,
-                        lr,
-                        Some(&iter_count)
-                    )
+                    positions_to_string(currents, &self.walk_map, iter_count, lr, Some(iter_count))
                 );
                 for current in currents {
                     *current = self.walk_map.access(current).access(lr);
@@ -763,8 +1301,7 @@ fn positions_to_string<'a>(
         .iter()
         .map(|pos| {
             let already_counted = duplicate_map
-                .map(|duplicates| duplicates.get(pos))
-                .flatten()
+                .and_then(|duplicates| duplicates.get(pos))
                 .or(Some(&1));
             let left_right = walk_map.access(pos);
             format!(
@@ -774,13 +1311,13 @@ fn positions_to_string<'a>(
                     .conv::<ColoredString>()
                     .pipe_if(END_IN_A.is_match(pos), ColoredString::green)
                     .pipe_if(END_IN_Z.is_match(pos), ColoredString::red),
-                &left_right
+                left_right
                     .left
                     .conv::<ColoredString>()
                     .pipe_if(lr == 'L', ColoredString::bold)
                     .pipe_if(END_IN_A.is_match(left_right.left), ColoredString::green)
                     .pipe_if(END_IN_Z.is_match(left_right.left), ColoredString::red),
-                &left_right
+                left_right
                     .right
                     .conv::<ColoredString>()
                     .pipe_if(lr == 'R', ColoredString::bold)
@@ -798,16 +1335,227 @@ fn positions_to_string<'a>(
         .join("\t")
 }
 
+/**
+ * When a ghost is at Z$
+ */
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ZArrival {
+    pub steps: usize,
+    pub location: String,
+}
+
+/**
+ * How a single ghost gets to Z$
+ */
+#[derive(Debug, Serialize)]
+pub struct GhostAnalysis {
+    pub start: String,
+    pub first_z: Option<ZArrival>,
+    pub second_z: Option<ZArrival>,
+    /**
+     * Whether the ghost is at the same Z$ every `first_z` steps, at the start of the walk
+     * instructions each time. That's what `Strategy::Lcm` relies on
+     */
+    pub periodic: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Analysis {
+    pub locations: usize,
+    pub walk_instructions: usize,
+    pub ghosts: Vec<GhostAnalysis>,
+}
+
+impl Analysis {
+    pub fn lcm_applies(&self) -> bool {
+        self.ghosts.iter().all(|ghost| ghost.periodic)
+    }
+}
+
+/**
+ * The challenge input, in a shape that serializes nicely
+ */
+#[derive(Serialize)]
+pub struct Network<'a> {
+    pub walk_instructions: &'a str,
+    pub nodes: BTreeMap<&'a str, &'a LeftRight<'a>>,
+}
+
+/*
+ * Looking at the network instead of solving it. These walk step by step and are
+ * only fast enough for a few passes over the walk instructions
+ */
+impl<'a> Walker<'a> {
+    /**
+     * The first `count` times the ghost starting at `start` is at Z$. There are only so many
+     * combinations of location and position within the walk instructions, so we give up
+     * after twice that many steps
+     */
+    fn z_arrivals(&self, start: &'a str, count: usize) -> Vec<ZArrival> {
+        let give_up_after = 2 * self.walk_map.len() * self.walk_instructions_len;
+        let mut arrivals = vec![];
+        let mut current = start;
+        for (steps, lr) in (1..=give_up_after).zip(self.walk_instructions.chars().cycle()) {
+            current = self.walk_map.access(&current).access(lr);
+            if END_IN_Z.is_match(current) {
+                arrivals.push(ZArrival {
+                    steps,
+                    location: current.to_owned(),
+                });
+                if arrivals.len() == count {
+                    break;
+                }
+            }
+        }
+        arrivals
+    }
+
+    pub fn analyze(&self) -> Analysis {
+        let ghosts = self
+            .start_positions
+            .iter()
+            .map(|start| {
+                let mut arrivals = self.z_arrivals(start, 2).into_iter();
+                let first_z = arrivals.next();
+                let second_z = arrivals.next();
+                let periodic = match (&first_z, &second_z) {
+                    (Some(first), Some(second)) => {
+                        second.steps == 2 * first.steps
+                            && second.location == first.location
+                            && first.steps.is_multiple_of(self.walk_instructions_len)
+                    }
+                    _ => false,
+                };
+                GhostAnalysis {
+                    start: start.to_string(),
+                    first_z,
+                    second_z,
+                    periodic,
+                }
+            })
+            .collect();
+        Analysis {
+            locations: self.walk_map.len(),
+            walk_instructions: self.walk_instructions_len,
+            ghosts,
+        }
+    }
+
+    pub fn network(&self) -> Network<'_> {
+        Network {
+            walk_instructions: self.walk_instructions,
+            nodes: self.walk_map.iter().map(|(key, lr)| (*key, lr)).collect(),
+        }
+    }
+
+    /**
+     * The network as a Graphviz graph, colored like the step by step output
+     */
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph network {\n");
+        for (location, left_right) in self.network().nodes {
+            if END_IN_A.is_match(location) {
+                dot += &format!("    \"{location}\" [color=green];\n");
+            } else if END_IN_Z.is_match(location) {
+                dot += &format!("    \"{location}\" [color=red];\n");
+            }
+            dot += &format!("    \"{location}\" -> \"{}\" [label=L];\n", left_right.left);
+            dot += &format!(
+                "    \"{location}\" -> \"{}\" [label=R];\n",
+                left_right.right
+            );
+        }
+        dot + "}\n"
+    }
+}
+
+/**
+ * Internals the benchmarks need to measure, but which are not part of the public API
+ */
+#[doc(hidden)]
+pub mod bench_support {
+    use super::*;
+
+    /**
+     * Everything the jump maps are created from, computed in advance so that the
+     * benchmark measures nothing but the creation of the jump map
+     */
+    pub struct JumpMapInput<'a> {
+        walk_instructions: &'a str,
+        left_right_map: MyMap<&'a str, LeftRight<'a>>,
+        successors: Vec<(usize, usize)>,
+        max_distance: usize,
+    }
+
+    impl<'a> JumpMapInput<'a> {
+        pub fn new(input: &'a str) -> Self {
+            let (walk_instructions, left_right_map) =
+                Walker::parse_challenge(input).expect("Benchmark inputs are valid");
+            let itertools: Vec<Itertool> = left_right_map
+                .keys()
+                .map(|key| Walker::create_itertool(key, walk_instructions, &left_right_map))
+                .collect();
+            let max_distance = max_next_z(&itertools);
+            Self {
+                walk_instructions,
+                successors: PowerWalker::index_successors(
+                    &left_right_map,
+                    &PowerWalker::index_locations(&left_right_map),
+                ),
+                left_right_map,
+                max_distance,
+            }
+        }
+
+        /**
+         * Returns the number of locations in the jump map
+         */
+        pub fn create_walker_jump_map(&self) -> usize {
+            Walker::create_jump_map(
+                &self.left_right_map,
+                self.walk_instructions,
+                self.max_distance,
+            )
+            .len()
+        }
+
+        /**
+         * Returns the number of locations in the jump map
+         */
+        pub fn create_power_walker_jump_map(&self) -> usize {
+            PowerWalker::create_jump_map(
+                self.walk_instructions,
+                &self.successors,
+                self.max_distance,
+            )
+            .len()
+        }
+    }
+
+    pub fn walk_by_jump_map(walker: &Walker) -> usize {
+        walker.walk_by_jump_map(Strategy::current())
+    }
+
+    pub fn power_walk_by_jump_map(walker: &PowerWalker) -> usize {
+        walker.walk_by_jump_map(Strategy::current())
+    }
+}
+
 lazy_static! {
     static ref END_IN_Z: Regex = Regex::new("Z$").unwrap();
     static ref END_IN_A: Regex = Regex::new("A$").unwrap();
+    static ref IS_ZZZ: Regex = Regex::new("^ZZZ$").unwrap();
     static ref MAP_PARSER: Regex =
         Regex::new("^([A-Z0-9]{3}) = \\(([A-Z0-9]{3}), ([A-Z0-9]{3})\\)$").unwrap();
 }
 
+#[cfg(test)]
+mod differential_tests;
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::progress::ProgressCallback;
 
     #[cfg(any(feature = "medium_test", feature = "heavy_test"))]
     static INPUT: &str = include_str!("../../challenge.txt");
@@ -816,6 +1564,225 @@ mod tests {
     #[cfg(feature = "heavy_test")]
     const PART2: usize = 10668805667831;
 
+    static PART_2_EXAMPLE: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+
+    fn cancel_at_first_report<'a>(solver: &impl AOC8Solver<'a>) -> Checkpoint {
+        let mut cancel = ProgressCallback {
+            every: 1,
+            callback: |_: &Progress| ControlFlow::Break(()),
+        };
+        match solver.solve_part_2_observed(Strategy::JumpMap, &mut cancel) {
+            WalkOutcome::Cancelled(checkpoint) => checkpoint,
+            finished => panic!("Expected the walk to be cancelled, but it {finished:?}"),
+        }
+    }
+
+    #[test]
+    fn cancelled_walk_can_be_resumed() {
+        let walker = Walker::new(PART_2_EXAMPLE);
+        let power_walker = PowerWalker::new(PART_2_EXAMPLE);
+        let expected_checkpoint = Checkpoint {
+            steps: 2,
+            positions: vec!["11Z".to_owned(), "22C".to_owned()],
+        };
+        let mut reported_steps = vec![];
+        let mut observer = ProgressCallback {
+            every: 1,
+            callback: |progress: &Progress| {
+                reported_steps.push(progress.steps);
+                ControlFlow::Continue(())
+            },
+        };
+
+        let mut checkpoint = cancel_at_first_report(&walker);
+        checkpoint.positions.sort();
+        assert_eq!(checkpoint, expected_checkpoint);
+        assert_eq!(
+            walker
+                .resume_part_2(&checkpoint, Strategy::JumpMap, &mut observer)
+                .unwrap(),
+            WalkOutcome::Finished(6)
+        );
+
+        let mut checkpoint = cancel_at_first_report(&power_walker);
+        checkpoint.positions.sort();
+        assert_eq!(checkpoint, expected_checkpoint);
+        assert_eq!(
+            power_walker
+                .resume_part_2(&checkpoint, Strategy::JumpMap, &mut observer)
+                .unwrap(),
+            WalkOutcome::Finished(6)
+        );
+
+        assert_eq!(reported_steps, vec![4, 6, 4, 6]);
+    }
+
+    /**
+     * Never reports progress, but wants to stop as soon as it is asked
+     */
+    struct StopAtOnce;
+
+    impl WalkObserver for StopAtOnce {
+        fn report_every(&self) -> usize {
+            usize::MAX
+        }
+
+        fn on_progress(&mut self, _: &Progress) -> ControlFlow<()> {
+            unreachable!("Progress is never reported")
+        }
+
+        fn stop_check_every(&self) -> usize {
+            1
+        }
+
+        fn should_stop(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn walks_stop_between_reports() {
+        for outcome in [
+            Walker::new(PART_2_EXAMPLE).solve_part_2_observed(Strategy::JumpMap, &mut StopAtOnce),
+            PowerWalker::new(PART_2_EXAMPLE)
+                .solve_part_2_observed(Strategy::JumpMap, &mut StopAtOnce),
+        ] {
+            let WalkOutcome::Cancelled(mut checkpoint) = outcome else {
+                panic!("Expected the walk to be cancelled, but it {outcome:?}");
+            };
+            checkpoint.positions.sort();
+            assert_eq!(
+                checkpoint,
+                Checkpoint {
+                    steps: 2,
+                    positions: vec!["11Z".to_owned(), "22C".to_owned()],
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_checkpoints_are_rejected() {
+        let walker = Walker::new(PART_2_EXAMPLE);
+        let mut observer = ProgressCallback {
+            every: 1,
+            callback: |_: &Progress| ControlFlow::Continue(()),
+        };
+        let mut resume = |steps: usize, positions: &[&str]| {
+            let checkpoint = Checkpoint {
+                steps,
+                positions: positions.iter().map(|p| p.to_string()).collect(),
+            };
+            walker.resume_part_2(&checkpoint, Strategy::JumpMap, &mut observer)
+        };
+
+        assert!(matches!(
+            resume(2, &["11Z", "ABC"]),
+            Err(InvalidCheckpoint::UnknownLocation(_))
+        ));
+        assert!(matches!(
+            resume(2, &["11Z"]),
+            Err(InvalidCheckpoint::WrongNumberOfGhosts { .. })
+        ));
+        assert!(matches!(
+            resume(3, &["11Z", "22C"]),
+            Err(InvalidCheckpoint::NotAtStartOfInstructions { .. })
+        ));
+    }
+
+    #[test]
+    fn analysis_tells_whether_lcm_applies() {
+        let analysis = Walker::new(PART_2_EXAMPLE).analyze();
+        let mut ghosts = analysis.ghosts;
+        ghosts.sort_by(|a, b| a.start.cmp(&b.start));
+
+        assert_eq!(ghosts[0].start, "11A");
+        assert_eq!(
+            ghosts[0].first_z,
+            Some(ZArrival {
+                steps: 2,
+                location: "11Z".to_owned()
+            })
+        );
+        assert!(ghosts[0].periodic);
+        // Arrives after 3 and 6 steps, but in the middle of the walk instructions
+        assert_eq!(ghosts[1].second_z.as_ref().map(|z| z.steps), Some(6));
+        assert!(!ghosts[1].periodic);
+    }
+
+    #[test]
+    fn malformed_challenges_are_rejected() {
+        let error = |input| PowerWalker::parse(input).err().unwrap();
+
+        assert_eq!(
+            error("LX\n\nAAA = (AAA, AAA)\n"),
+            ParseError::new("\"X\" is neither \"L\" nor \"R\"").at(1, 2)
+        );
+        assert_eq!(
+            error("LR\n\nAAA = (AAA, AAA)\nZZZ (AAA, AAA)\n").line,
+            Some(4)
+        );
+        assert_eq!(
+            error("LR\n\nAAA = (AAA, ZZZ)\n").to_string(),
+            "AAA leads to ZZZ, which is not on the map"
+        );
+        assert!(Walker::parse("LR").is_err());
+    }
+
+    #[test]
+    fn solver_answers_all_parts() {
+        let walker = PowerWalker::parse(PART_2_EXAMPLE).unwrap();
+
+        assert_eq!(
+            walker.part_1(),
+            Err(NoSolution(NO_WAY_FROM_AAA_TO_ZZZ.to_owned()))
+        );
+        assert_eq!(walker.solve(2), Some(Ok(Answer::Int(6))));
+        // The lcm happens to be right, but the ghost from 22A is not periodic
+        assert_eq!(
+            walker.solve(3),
+            Some(Err(NoSolution(NOT_PERIODIC.to_owned())))
+        );
+        assert_eq!(walker.solve(4), None);
+    }
+
+    #[test]
+    fn lcm_is_only_taken_for_periodic_ghosts() {
+        let periodic = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (XXX, 22C)
+22C = (22D, XXX)
+22D = (XXX, 22Z)
+22Z = (22B, XXX)
+XXX = (XXX, XXX)
+";
+        assert_eq!(Walker::new(periodic).extra_part(3), Ok(Answer::Int(4)));
+        assert_eq!(PowerWalker::new(periodic).extra_part(3), Ok(Answer::Int(4)));
+
+        // 11Z is reached after 2 steps, but then never again
+        let once = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (XXX, XXX)\nXXX = (XXX, XXX)\n";
+        for solver in [
+            Walker::new(once).extra_part(3),
+            PowerWalker::new(once).extra_part(3),
+        ] {
+            assert!(solver.is_err());
+        }
+    }
+
     #[test]
     fn lcm_works() {
         assert_eq!(least_common_multiple(vec![3, 7, 43]), 903);
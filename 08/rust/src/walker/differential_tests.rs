@@ -0,0 +1,234 @@
+//! Checks all solvers against each other on small random networks.
+//!
+//! Every network is solved by brute force, which simulates the ghosts step by step and is
+//! therefore obviously correct, if slow. The clever solvers must agree with it. Should they
+//! not, proptest shrinks the network and reports the smallest challenge input it could find
+//! that still shows the disagreement.
+
+use std::{collections::HashSet, fmt::Debug};
+
+use proptest::{prelude::*, strategy::Strategy as _};
+
+use super::*;
+use crate::progress::{ProgressCallback, Strategy};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Start,
+    End,
+    Other,
+}
+
+/// A challenge, as indices instead of names, so that proptest can shrink it
+#[derive(Clone)]
+struct Network {
+    instructions: Vec<char>,
+    /// Left and right successor of each node. Node 0 is `AAA`, node 1 is `ZZZ`
+    nodes: Vec<(usize, usize)>,
+    /// Kinds of all nodes but `AAA` and `ZZZ`
+    kinds: Vec<Kind>,
+}
+
+impl Network {
+    fn kind(&self, node: usize) -> Kind {
+        match node {
+            0 => Kind::Start,
+            1 => Kind::End,
+            _ => self.kinds[node - 2],
+        }
+    }
+
+    fn name(&self, node: usize) -> String {
+        match node {
+            0 => "AAA".to_owned(),
+            1 => "ZZZ".to_owned(),
+            _ => match self.kind(node) {
+                Kind::Start => format!("{node:02}A"),
+                Kind::End => format!("{node:02}Z"),
+                Kind::Other => format!("{node:02}X"),
+            },
+        }
+    }
+
+    fn to_challenge(&self) -> String {
+        let instructions: String = self.instructions.iter().collect();
+        let nodes = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(node, (left, right))| {
+                format!(
+                    "{} = ({}, {})",
+                    self.name(node),
+                    self.name(*left),
+                    self.name(*right)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!("{instructions}\n\n{nodes}\n")
+    }
+
+    fn next(&self, node: usize, steps: usize) -> usize {
+        match self.instructions[steps % self.instructions.len()] {
+            'L' => self.nodes[node].0,
+            _ => self.nodes[node].1,
+        }
+    }
+
+    fn starts(&self) -> Vec<usize> {
+        (0..self.nodes.len())
+            .filter(|node| self.kind(*node) == Kind::Start)
+            .collect()
+    }
+
+    /// After this many steps, a single ghost must have been in the same state twice
+    fn single_ghost_states(&self) -> usize {
+        self.nodes.len() * self.instructions.len()
+    }
+
+    fn brute_force_part_1(&self) -> Option<usize> {
+        let mut current = 0;
+        for steps in 0..=self.single_ghost_states() {
+            if current == 1 {
+                return Some(steps);
+            }
+            current = self.next(current, steps);
+        }
+        None
+    }
+
+    fn brute_force_part_2(&self) -> Option<usize> {
+        let mut currents = self.starts();
+        let mut seen = HashSet::new();
+        let mut steps = 0;
+        while seen.insert((currents.clone(), steps % self.instructions.len())) {
+            if currents.iter().all(|node| self.kind(*node) == Kind::End) {
+                return Some(steps);
+            }
+            for current in &mut currents {
+                *current = self.next(*current, steps);
+            }
+            steps += 1;
+        }
+        None
+    }
+
+    /// Whether the ghost starting at `start` is at Z$ after exactly every multiple of the
+    /// steps it takes to get there the first time. That's what the lcm approach relies on
+    fn is_periodic(&self, start: usize) -> bool {
+        let mut current = start;
+        let mut first_z = None;
+        for steps in 0..=2 * self.single_ghost_states() {
+            if steps > 0 && self.kind(current) == Kind::End {
+                match first_z {
+                    None => first_z = Some((steps, current)),
+                    Some((first_steps, first_node)) => {
+                        return steps == 2 * first_steps
+                            && current == first_node
+                            && steps % self.instructions.len()
+                                == first_steps % self.instructions.len();
+                    }
+                }
+            }
+            current = self.next(current, steps);
+        }
+        false
+    }
+}
+
+/// Print the challenge as it would appear in the input file
+impl Debug for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\n{}", self.to_challenge())
+    }
+}
+
+/// Cancels the walk at the first report, and then resumes it from the checkpoint
+fn walk_with_interruption<K>(walker: &impl AOC8Walker<K>, strategy: Strategy) -> usize {
+    let mut cancel = ProgressCallback {
+        every: 1,
+        callback: |_: &Progress| ControlFlow::Break(()),
+    };
+    let currents = walker.get_start_positions().iter().collect();
+    match walker.walk_by_jump_map_from(0, currents, strategy, &mut cancel) {
+        WalkOutcome::Finished(steps) => steps,
+        WalkOutcome::Cancelled(checkpoint) => {
+            let mut keep_going = ProgressCallback {
+                every: 1,
+                callback: |_: &Progress| ControlFlow::Continue(()),
+            };
+            match walker.resume_walk_by_jump_map(&checkpoint, strategy, &mut keep_going) {
+                Ok(WalkOutcome::Finished(steps)) => steps,
+                other => panic!("Could not resume from {checkpoint:?}: {other:?}"),
+            }
+        }
+    }
+}
+
+fn network() -> impl proptest::strategy::Strategy<Value = Network> {
+    (2..=6usize)
+        .prop_flat_map(|size| {
+            (
+                prop::collection::vec(prop_oneof![Just('L'), Just('R')], 1..=4),
+                prop::collection::vec((0..size, 0..size), size),
+                prop::collection::vec(
+                    prop_oneof![
+                        1 => Just(Kind::Start),
+                        2 => Just(Kind::End),
+                        1 => Just(Kind::Other)
+                    ],
+                    size - 2,
+                ),
+            )
+        })
+        .prop_map(|(instructions, nodes, kinds)| Network {
+            instructions,
+            nodes,
+            kinds,
+        })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2000))]
+
+    #[test]
+    fn part_1_agrees_with_brute_force(network in network()) {
+        let input = network.to_challenge();
+        let walker = Walker::new(&input);
+        let power_walker = PowerWalker::new(&input);
+
+        if let Some(expected) = network.brute_force_part_1() {
+            prop_assert_eq!(walker.solve_part_1(), expected);
+            prop_assert_eq!(power_walker.solve_part_1(), expected);
+        }
+    }
+
+    #[test]
+    fn part_2_agrees_with_brute_force(network in network()) {
+        let input = network.to_challenge();
+        let walker = Walker::new(&input);
+        let power_walker = PowerWalker::new(&input);
+
+        let Some(expected) = network.brute_force_part_2() else {
+            // The ghosts never meet, so every solver would walk forever
+            return Ok(());
+        };
+        let periodic = network.starts().into_iter().all(|start| network.is_periodic(start));
+        prop_assert_eq!(walker.try_solve_part_2(Strategy::Lcm).is_ok(), periodic);
+        prop_assert_eq!(power_walker.try_solve_part_2(Strategy::Lcm).is_ok(), periodic);
+
+        let mut strategies = vec![Strategy::JumpMap];
+        if periodic {
+            strategies.push(Strategy::JumpMapAssumingConstantZDistances);
+            prop_assert_eq!(walker.solve_by_lcm(), expected);
+            prop_assert_eq!(power_walker.solve_by_lcm(), expected);
+        }
+        for strategy in strategies {
+            prop_assert_eq!(walker.walk_by_jump_map(strategy), expected);
+            prop_assert_eq!(power_walker.walk_by_jump_map(strategy), expected);
+            prop_assert_eq!(walk_with_interruption(&walker, strategy), expected);
+            prop_assert_eq!(walk_with_interruption(&power_walker, strategy), expected);
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use aoc_core::{parse_lines, Answer, Day, NoSolution, ParseError, Registry, Solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /**
+     * The value after the last one, for part 1
+     */
+    Forwards,
+    /**
+     * The value before the first one, for part 2
+     */
+    Backwards,
+}
+
+/**
+ * The history of one value, measured at evenly spaced times
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    pub values: Vec<i64>,
+    /**
+     * Where the sequence is in the report, blank lines included
+     */
+    pub line_number: usize,
+}
+
+impl Sequence {
+    /**
+     * The sequence itself, followed by its differences until they are all 0, or only one
+     * is left. `None` if a difference does not fit into an i64
+     */
+    pub fn differences(&self) -> Option<Vec<Vec<i64>>> {
+        let mut rows = vec![self.values.clone()];
+        loop {
+            let last = rows.last()?;
+            if last.len() <= 1 || last.iter().all(|value| *value == 0) {
+                return Some(rows);
+            }
+            let next = last
+                .windows(2)
+                .map(|pair| pair[1].checked_sub(pair[0]))
+                .collect::<Option<Vec<_>>>()?;
+            rows.push(next);
+        }
+    }
+
+    /**
+     * The degree of the lowest polynomial through all values: how many times they have to be
+     * differenced until they are constant. Sequences with no such polynomial of a lower
+     * degree are one below their length
+     */
+    pub fn degree(&self) -> Option<usize> {
+        let rows = self.differences()?;
+        Some(
+            rows.iter()
+                .position(|row| row.windows(2).all(|pair| pair[0] == pair[1]))
+                .unwrap_or(rows.len() - 1),
+        )
+    }
+
+    /**
+     * Extends the sequence by one value, by extending each row of differences from the
+     * bottom up
+     */
+    pub fn extrapolate(&self, direction: Direction) -> Option<i64> {
+        let rows = self.differences()?;
+        rows.iter()
+            .rev()
+            .try_fold(0i64, |below, row| match direction {
+                Direction::Forwards => row.last()?.checked_add(below),
+                Direction::Backwards => row.first()?.checked_sub(below),
+            })
+    }
+
+    /**
+     * Like `extrapolate`, but without the rows of differences. The `n` values lie on a
+     * polynomial of degree below `n`, so its `n`th differences are 0. Written out with the
+     * binomial coefficients, that gives
+     * `y(n) = Σ (-1)^(n - 1 - i) * C(n, i) * y(i)` and `y(-1) = Σ (-1)^i * C(n, i + 1) * y(i)`,
+     * which is Lagrange interpolation at evenly spaced points
+     */
+    pub fn extrapolate_closed_form(&self, direction: Direction) -> Option<i64> {
+        let n = self.values.len() as i128;
+        let mut binomial = 1i128;
+        let mut sum = 0i128;
+        // C(n, k) for k from 0 to n
+        let mut binomials = vec![binomial];
+        for k in 0..n {
+            binomial = binomial.checked_mul(n - k)? / (k + 1);
+            binomials.push(binomial);
+        }
+        for (i, value) in self.values.iter().enumerate() {
+            let (coefficient, negative) = match direction {
+                Direction::Forwards => (binomials[i], (n - 1 - i as i128) % 2 == 1),
+                Direction::Backwards => (binomials[i + 1], i % 2 == 1),
+            };
+            let term = coefficient.checked_mul(i128::from(*value))?;
+            sum = if negative {
+                sum.checked_sub(term)?
+            } else {
+                sum.checked_add(term)?
+            };
+        }
+        i64::try_from(sum).ok()
+    }
+}
+
+/**
+ * The report of the Oasis And Sand Instability Sensor
+ */
+pub struct Report {
+    pub sequences: Vec<Sequence>,
+}
+
+impl Report {
+    /**
+     * The sum of all extrapolated values, with the line of the first one that overflows
+     * as error
+     */
+    pub fn sum(&self, direction: Direction) -> Result<i64, NoSolution> {
+        self.sequences.iter().try_fold(0i64, |sum, sequence| {
+            sequence
+                .extrapolate(direction)
+                .and_then(|value| sum.checked_add(value))
+                .ok_or_else(|| {
+                    NoSolution(format!(
+                        "The sum overflows at the sequence in line {}",
+                        sequence.line_number
+                    ))
+                })
+        })
+    }
+}
+
+impl Solver<'_> for Report {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Mirage Maintenance";
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let sequences = parse_lines(input, "The report is empty", |line, line_number| {
+            line.split_whitespace()
+                .map(|number| {
+                    number.parse().map_err(|_| {
+                        ParseError::new(format!("Expected a number, found \"{number}\""))
+                            .at_line(line_number)
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(|values| Sequence {
+                    values,
+                    line_number,
+                })
+        })?;
+        Ok(Self { sequences })
+    }
+
+    fn part_1(&self) -> Result<Answer, NoSolution> {
+        self.sum(Direction::Forwards).map(Answer::from)
+    }
+
+    fn part_2(&self) -> Result<Answer, NoSolution> {
+        self.sum(Direction::Backwards).map(Answer::from)
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::of::<Report>());
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/example.txt");
+
+    fn sequence(values: &[i64]) -> Sequence {
+        Sequence {
+            values: values.to_vec(),
+            line_number: 1,
+        }
+    }
+
+    #[test]
+    fn the_degree_is_where_differences_become_constant() {
+        let report = Report::parse(EXAMPLE).unwrap();
+        let degrees: Vec<_> = report
+            .sequences
+            .iter()
+            .map(|sequence| sequence.degree())
+            .collect();
+        assert_eq!(degrees, vec![Some(1), Some(2), Some(3)]);
+        assert_eq!(sequence(&[0, 0, 0]).degree(), Some(0));
+        assert_eq!(sequence(&[7]).degree(), Some(0));
+        // Three values that need a parabola
+        assert_eq!(sequence(&[1, 5, 2]).degree(), Some(2));
+    }
+
+    #[test]
+    fn both_methods_extrapolate_both_ways() {
+        let values = sequence(&[10, 13, 16, 21, 30, 45]);
+        for extrapolate in [Sequence::extrapolate, Sequence::extrapolate_closed_form] {
+            assert_eq!(extrapolate(&values, Direction::Forwards), Some(68));
+            assert_eq!(extrapolate(&values, Direction::Backwards), Some(5));
+        }
+    }
+
+    #[test]
+    fn overflows_are_reported() {
+        let values = sequence(&[i64::MIN, i64::MAX]);
+        assert_eq!(values.extrapolate(Direction::Forwards), None);
+        assert_eq!(values.extrapolate_closed_form(Direction::Forwards), None);
+        // The binomial coefficients of long sequences overflow, their differences do not
+        let values = sequence(&[1; 200]);
+        assert_eq!(values.extrapolate(Direction::Backwards), Some(1));
+        assert_eq!(values.extrapolate_closed_form(Direction::Backwards), None);
+    }
+
+    #[test]
+    fn overflowing_sums_name_the_line_of_the_sequence() {
+        let report = Report::parse(&format!("1 2 3\n\n{} {}\n", i64::MAX - 1, i64::MAX)).unwrap();
+        assert_eq!(
+            report.sum(Direction::Forwards),
+            Err(NoSolution(
+                "The sum overflows at the sequence in line 3".to_owned()
+            ))
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn both_methods_agree_on_polynomials(
+            coefficients in prop::collection::vec(-50i64..50, 1..6),
+            length in 1usize..15,
+            start in -20i64..20,
+        ) {
+            let polynomial = |x: i64| coefficients.iter().rev().fold(0, |sum, c| sum * x + c);
+            let values = sequence(&(0..length as i64).map(|x| polynomial(start + x)).collect::<Vec<_>>());
+            if length > coefficients.len() {
+                prop_assert_eq!(values.extrapolate(Direction::Forwards), Some(polynomial(start + length as i64)));
+                prop_assert_eq!(values.extrapolate(Direction::Backwards), Some(polynomial(start - 1)));
+            }
+            for direction in [Direction::Forwards, Direction::Backwards] {
+                prop_assert_eq!(values.extrapolate(direction), values.extrapolate_closed_form(direction));
+            }
+        }
+    }
+}
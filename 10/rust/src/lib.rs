@@ -0,0 +1,292 @@
+use std::fmt::Display;
+
+use aoc_core::{Answer, Coord, Day, Grid, NoSolution, ParseError, Registry, Solver};
+
+/**
+ * The neighbours a pipe connects to, one bit for each direction
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tile(u8);
+
+impl Tile {
+    pub const NORTH: u8 = 1;
+    pub const EAST: u8 = 2;
+    pub const SOUTH: u8 = 4;
+    pub const WEST: u8 = 8;
+    pub const DIRECTIONS: [u8; 4] = [Self::NORTH, Self::EAST, Self::SOUTH, Self::WEST];
+
+    pub const GROUND: Tile = Tile(0);
+
+    pub fn from_char(char: char) -> Option<Self> {
+        let connections = match char {
+            '|' => Self::NORTH | Self::SOUTH,
+            '-' => Self::EAST | Self::WEST,
+            'L' => Self::NORTH | Self::EAST,
+            'J' => Self::NORTH | Self::WEST,
+            '7' => Self::SOUTH | Self::WEST,
+            'F' => Self::SOUTH | Self::EAST,
+            '.' => 0,
+            _ => return None,
+        };
+        Some(Tile(connections))
+    }
+
+    pub fn connects(&self, direction: u8) -> bool {
+        self.0 & direction != 0
+    }
+
+    /**
+     * How a pipe with these connections is drawn with box-drawing characters
+     */
+    pub fn box_drawing(&self) -> char {
+        match self.0 {
+            0b0101 => '│',
+            0b1010 => '─',
+            0b0011 => '└',
+            0b1001 => '┘',
+            0b1100 => '┐',
+            0b0110 => '┌',
+            0 => ' ',
+            _ => '?',
+        }
+    }
+}
+
+/**
+ * The direction that leads back
+ */
+fn opposite(direction: u8) -> u8 {
+    (direction << 2 | direction >> 2) & 0b1111
+}
+
+fn step(tiles: &Grid<Tile>, from: Coord, direction: u8) -> Option<Coord> {
+    let (rows, columns) = match direction {
+        Tile::NORTH => (-1, 0),
+        Tile::EAST => (0, 1),
+        Tile::SOUTH => (1, 0),
+        _ => (0, -1),
+    };
+    tiles.offset(from, rows, columns)
+}
+
+/**
+ * The field of pipes, with the animal's loop traced through it
+ */
+#[derive(Debug)]
+pub struct Maze {
+    pub tiles: Grid<Tile>,
+    /**
+     * Its tile is inferred from the pipes that connect to it
+     */
+    pub start: Coord,
+    /**
+     * All tiles of the loop in order, beginning with `start`
+     */
+    pub main_loop: Vec<Coord>,
+}
+
+impl Maze {
+    /**
+     * The start connects to two of the pipes around it that lead back to it. Pipes off the
+     * loop can point at it too, so every pair of them is tried until one closes the loop.
+     * Sets the start's tile to that pair, and returns the loop
+     */
+    fn infer_start(tiles: &mut Grid<Tile>, start: Coord) -> Result<Vec<Coord>, ParseError> {
+        let connecting: Vec<u8> = Tile::DIRECTIONS
+            .into_iter()
+            .filter(|direction| {
+                step(tiles, start, *direction)
+                    .is_some_and(|neighbour| tiles[neighbour].connects(opposite(*direction)))
+            })
+            .collect();
+        if connecting.len() < 2 {
+            return Err(ParseError::new(format!(
+                "Expected two pipes to connect to the start, found {}",
+                connecting.len()
+            ))
+            .at(start.row + 1, start.column + 1));
+        }
+        let mut broken = None;
+        for (index, first) in connecting.iter().enumerate() {
+            for second in &connecting[index + 1..] {
+                tiles[start] = Tile(first | second);
+                match Self::trace_loop(tiles, start) {
+                    Ok(main_loop) => return Ok(main_loop),
+                    Err(error) => {
+                        broken.get_or_insert(error);
+                    }
+                }
+            }
+        }
+        Err(match broken {
+            // With only one pair to try, where it breaks is the more helpful error
+            Some(error) if connecting.len() == 2 => error,
+            _ => ParseError::new(format!(
+                "None of the {} pipes that connect to the start lead back to it",
+                connecting.len()
+            ))
+            .at(start.row + 1, start.column + 1),
+        })
+    }
+
+    /**
+     * Follows the pipes from `start` until they lead back to it
+     */
+    fn trace_loop(tiles: &Grid<Tile>, start: Coord) -> Result<Vec<Coord>, ParseError> {
+        let mut main_loop = vec![start];
+        let mut at = start;
+        let mut direction = Tile::DIRECTIONS
+            .into_iter()
+            .find(|direction| tiles[start].connects(*direction))
+            .expect("The start has two connections");
+        loop {
+            let next = step(tiles, at, direction)
+                .filter(|next| tiles[*next].connects(opposite(direction)))
+                .ok_or_else(|| {
+                    ParseError::new("The loop is broken after this pipe")
+                        .at(at.row + 1, at.column + 1)
+                })?;
+            if next == start {
+                return Ok(main_loop);
+            }
+            direction = tiles[next].0 & !opposite(direction);
+            main_loop.push(next);
+            at = next;
+        }
+    }
+
+    /**
+     * The tile of the loop that takes the most steps to reach from the start, in either
+     * direction, and these steps
+     */
+    pub fn farthest_point(&self) -> (Coord, usize) {
+        let steps = self.main_loop.len() / 2;
+        (self.main_loop[steps], steps)
+    }
+
+    /**
+     * How many tiles the loop encloses. The shoelace formula gives the area of the polygon
+     * through the centers of the loop's tiles. Pick's theorem,
+     * `area = inside + boundary / 2 - 1`, then tells how many tiles lie inside
+     */
+    pub fn enclosed_tiles(&self) -> usize {
+        let corners = self
+            .main_loop
+            .iter()
+            .zip(self.main_loop.iter().cycle().skip(1));
+        let twice_area = corners
+            .map(|(a, b)| a.row as i64 * b.column as i64 - b.row as i64 * a.column as i64)
+            .sum::<i64>()
+            .unsigned_abs() as usize;
+        (twice_area + 2 - self.main_loop.len()) / 2
+    }
+
+    /**
+     * The loop drawn with box-drawing characters, leaving out all other pipes
+     */
+    pub fn render(&self) -> String {
+        let mut drawn = self.tiles.map(|_, _| ' ');
+        for at in &self.main_loop {
+            drawn[*at] = self.tiles[*at].box_drawing();
+        }
+        drawn.to_string()
+    }
+}
+
+impl Display for Maze {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+impl Solver<'_> for Maze {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Pipe Maze";
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut start = None;
+        let mut tiles = Grid::parse_with(input, |char, at| {
+            if char == 'S' {
+                if start.is_some() {
+                    return Err(ParseError::new("There is more than one start"));
+                }
+                start = Some(at);
+                return Ok(Tile::GROUND);
+            }
+            Tile::from_char(char)
+                .ok_or_else(|| ParseError::new(format!("Expected a pipe, found '{char}'")))
+        })?;
+        let start = start.ok_or_else(|| ParseError::new("There is no start"))?;
+        let main_loop = Self::infer_start(&mut tiles, start)?;
+        Ok(Self {
+            tiles,
+            start,
+            main_loop,
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer, NoSolution> {
+        Ok(self.farthest_point().1.into())
+    }
+
+    fn part_2(&self) -> Result<Answer, NoSolution> {
+        Ok(self.enclosed_tiles().into())
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::of::<Maze>());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: &str = include_str!("../../examples/square.txt");
+    const COMPLEX: &str = include_str!("../../examples/complex.txt");
+    const STRAY: &str = include_str!("../../examples/stray.txt");
+
+    #[test]
+    fn the_start_tile_is_inferred() {
+        let maze = Maze::parse(SQUARE).unwrap();
+        assert_eq!(maze.start, Coord::new(1, 1));
+        assert_eq!(maze.tiles[maze.start], Tile::from_char('F').unwrap());
+        assert_eq!(maze.main_loop.len(), 8);
+        assert_eq!(maze.farthest_point(), (Coord::new(3, 3), 4));
+        assert_eq!(opposite(Tile::EAST), Tile::WEST);
+        assert_eq!(opposite(Tile::SOUTH), Tile::NORTH);
+    }
+
+    #[test]
+    fn pipes_off_the_loop_may_point_at_the_start() {
+        let maze = Maze::parse(STRAY).unwrap();
+        assert_eq!(maze.tiles[maze.start], Tile::from_char('|').unwrap());
+        assert_eq!(maze.main_loop.len(), 8);
+        assert_eq!(
+            Maze::parse("-S-\n.|.\n").unwrap_err().to_string(),
+            "1:2: None of the 3 pipes that connect to the start lead back to it"
+        );
+    }
+
+    #[test]
+    fn only_the_loop_is_rendered() {
+        assert_eq!(
+            Maze::parse(COMPLEX).unwrap().render(),
+            "  ┌┐ \n ┌┘│ \n┌┘ └┐\n│┌──┘\n└┘   "
+        );
+    }
+
+    #[test]
+    fn broken_loops_are_located() {
+        let error = |input| Maze::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            error("S-7\n|.|\nL-.\n"),
+            "2:3: The loop is broken after this pipe"
+        );
+        assert_eq!(
+            error("S-7\n...\n"),
+            "1:1: Expected two pipes to connect to the start, found 1"
+        );
+        assert_eq!(error("S-X\n"), "1:3: Expected a pipe, found 'X'");
+    }
+}
@@ -1,7 +0,0 @@
-{
-  projectRootFile = "Cargo.toml";
-  programs = {
-    alejandra.enable = true;
-    rustfmt.enable = true;
-  };
-}
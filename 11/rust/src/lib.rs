@@ -0,0 +1,154 @@
+use aoc_core::{Answer, Coord, Day, Grid, NoSolution, ParseError, Registry, Solver};
+
+/**
+ * How many rows or columns each empty one becomes in part 1
+ */
+pub const YOUNG_EXPANSION: u64 = 2;
+/**
+ * How many rows or columns each empty one becomes in part 2
+ */
+pub const OLD_EXPANSION: u64 = 1_000_000;
+
+/**
+ * The sum of the distances between all pairs of `positions` along one axis, after every
+ * empty position before them grew to `expansion` positions. Sorts the positions, so that each
+ * one's distance to all before it is `index * position - sum of the ones before`
+ */
+fn axis_distance_sum(mut positions: Vec<usize>, expansion: u64) -> u128 {
+    positions.sort_unstable();
+    let expansion = u128::from(expansion);
+    let mut occupied_before = 0;
+    let mut previous = None;
+    let mut prefix_sum = 0u128;
+    let mut sum = 0u128;
+    for (index, position) in positions.iter().enumerate() {
+        if previous.is_some_and(|previous| previous != *position) {
+            occupied_before += 1;
+        }
+        previous = Some(*position);
+        let empty_before = (*position - occupied_before) as u128;
+        let expanded = occupied_before as u128 + empty_before * expansion;
+        sum += index as u128 * expanded - prefix_sum;
+        prefix_sum += expanded;
+    }
+    sum
+}
+
+/**
+ * The image of the universe, reduced to where its galaxies are
+ */
+pub struct Image {
+    pub galaxies: Vec<Coord>,
+}
+
+impl Image {
+    /**
+     * The sum of the shortest paths between all pairs of galaxies, with every row and
+     * column without a galaxy grown to `expansion` of them
+     */
+    pub fn distance_sum(&self, expansion: u64) -> u128 {
+        let rows = self.galaxies.iter().map(|galaxy| galaxy.row).collect();
+        let columns = self.galaxies.iter().map(|galaxy| galaxy.column).collect();
+        axis_distance_sum(rows, expansion) + axis_distance_sum(columns, expansion)
+    }
+}
+
+impl Solver<'_> for Image {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Cosmic Expansion";
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse_with(input, |char, _| match char {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(ParseError::new(format!(
+                "Expected '#' or '.', found '{char}'"
+            ))),
+        })?;
+        Ok(Self {
+            galaxies: grid
+                .iter()
+                .filter(|(_, galaxy)| **galaxy)
+                .map(|(at, _)| at)
+                .collect(),
+        })
+    }
+
+    fn part_1(&self) -> Result<Answer, NoSolution> {
+        Ok(self.distance_sum(YOUNG_EXPANSION).into())
+    }
+
+    fn part_2(&self) -> Result<Answer, NoSolution> {
+        Ok(self.distance_sum(OLD_EXPANSION).into())
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::of::<Image>());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/example.txt");
+
+    /**
+     * Expands every galaxy on its own and measures each pair
+     */
+    fn brute_force(image: &Image, expansion: u64) -> u128 {
+        let expand = |position: usize, occupied: &dyn Fn(usize) -> bool| {
+            (0..position)
+                .map(|before| {
+                    if occupied(before) {
+                        1
+                    } else {
+                        u128::from(expansion)
+                    }
+                })
+                .sum::<u128>()
+        };
+        let expanded: Vec<_> = image
+            .galaxies
+            .iter()
+            .map(|galaxy| {
+                (
+                    expand(galaxy.row, &|row| {
+                        image.galaxies.iter().any(|other| other.row == row)
+                    }),
+                    expand(galaxy.column, &|column| {
+                        image.galaxies.iter().any(|other| other.column == column)
+                    }),
+                )
+            })
+            .collect();
+        let mut sum = 0;
+        for (index, (row, column)) in expanded.iter().enumerate() {
+            for (other_row, other_column) in &expanded[index + 1..] {
+                sum += row.abs_diff(*other_row) + column.abs_diff(*other_column);
+            }
+        }
+        sum
+    }
+
+    #[test]
+    fn any_expansion_matches_the_pairwise_distances() {
+        let image = Image::parse(EXAMPLE).unwrap();
+        assert_eq!(image.distance_sum(10), 1030);
+        assert_eq!(image.distance_sum(100), 8410);
+        for expansion in [0, 1, 2, 7, 1_000_000, u64::MAX] {
+            assert_eq!(
+                image.distance_sum(expansion),
+                brute_force(&image, expansion),
+                "{expansion}"
+            );
+        }
+    }
+
+    #[test]
+    fn galaxies_in_the_same_row_share_its_expansion() {
+        let image = Image::parse("#.#\n...\n#..\n").unwrap();
+        // The empty row and column grow to 3 each, which puts the galaxies 4 apart
+        assert_eq!(image.distance_sum(3), 4 + 4 + 8);
+    }
+}
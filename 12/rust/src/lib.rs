@@ -0,0 +1,369 @@
+use std::{fmt::Display, thread};
+
+use aoc_core::{parse_lines, Answer, Day, NoSolution, ParseError, Registry, Solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Spring {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+impl Spring {
+    pub fn from_char(char: char) -> Option<Self> {
+        match char {
+            '.' => Some(Spring::Operational),
+            '#' => Some(Spring::Damaged),
+            '?' => Some(Spring::Unknown),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Spring {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let char = match self {
+            Spring::Operational => '.',
+            Spring::Damaged => '#',
+            Spring::Unknown => '?',
+        };
+        write!(f, "{char}")
+    }
+}
+
+/**
+ * One row of the condition records: the springs, and the sizes of the groups of damaged
+ * ones among them
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub springs: Vec<Spring>,
+    pub groups: Vec<usize>,
+}
+
+impl Row {
+    /**
+     * Parses a line like `???.### 1,1,3`
+     */
+    pub fn parse(line: &str, line_number: usize) -> Result<Self, ParseError> {
+        let error =
+            |column: usize, message: String| ParseError::new(message).at(line_number, column);
+        let (springs, groups) = line
+            .split_once(' ')
+            .ok_or_else(|| error(1, "Expected \"<springs> <groups>\"".to_owned()))?;
+        let groups_column = springs.chars().count() + 2;
+        let springs = springs
+            .chars()
+            .enumerate()
+            .map(|(index, char)| {
+                Spring::from_char(char).ok_or_else(|| {
+                    error(
+                        index + 1,
+                        format!("Expected '.', '#' or '?', found '{char}'"),
+                    )
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let groups = groups
+            .split(',')
+            .map(|group| match group.parse() {
+                Ok(size) if size > 0 => Ok(size),
+                _ => Err(error(
+                    groups_column,
+                    format!("Expected the sizes of the groups, found \"{group}\""),
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { springs, groups })
+    }
+
+    /**
+     * The row repeated `factor` times, with an unknown spring between the copies, like part
+     * 2 asks for
+     */
+    pub fn unfold(&self, factor: usize) -> Row {
+        let mut springs = Vec::with_capacity((self.springs.len() + 1) * factor);
+        for copy in 0..factor {
+            if copy > 0 {
+                springs.push(Spring::Unknown);
+            }
+            springs.extend_from_slice(&self.springs);
+        }
+        Row {
+            springs,
+            groups: self.groups.repeat(factor),
+        }
+    }
+
+    /**
+     * How many arrangements of damaged and operational springs fit the groups. `None` if
+     * there are more than a u128 can count
+     */
+    pub fn arrangements(&self) -> Option<u128> {
+        Arrangements::new(self).map(|arrangements| arrangements.count())
+    }
+
+    /**
+     * The arrangement at `index`, in the order of putting operational springs before
+     * damaged ones, left to right. `None` if there are not that many, or too many to count
+     */
+    pub fn arrangement(&self, index: u128) -> Option<String> {
+        Arrangements::new(self)?.nth(index)
+    }
+
+    /**
+     * Up to `limit` arrangements, spread evenly over all of them. Meant for looking into
+     * rows whose count seems off, when listing them all would take too long. `None` if there
+     * are too many to count
+     */
+    pub fn sample(&self, limit: usize) -> Option<Vec<String>> {
+        let arrangements = Arrangements::new(self)?;
+        let count = arrangements.count();
+        let limit = count.min(limit as u128);
+        // Dividing first cannot overflow, and only leaves out some of the last ones
+        let spacing = count.checked_div(limit).unwrap_or(0);
+        Some(
+            (0..limit)
+                .filter_map(|sample| arrangements.nth(sample * spacing))
+                .collect(),
+        )
+    }
+}
+
+/**
+ * The number of ways to arrange the springs from each position on, with the groups from
+ * each index on
+ */
+struct Arrangements<'a> {
+    row: &'a Row,
+    /**
+     * Indexed by position, then group, with `row.groups.len() + 1` groups per position
+     */
+    ways: Vec<u128>,
+}
+
+impl<'a> Arrangements<'a> {
+    /**
+     * `None` if a count does not fit into a u128
+     */
+    fn new(row: &'a Row) -> Option<Self> {
+        let springs = &row.springs;
+        let groups = row.groups.len() + 1;
+        // How many operational springs are before each position, to tell in O(1) whether
+        // a group fits somewhere
+        let mut operational_before = vec![0; springs.len() + 1];
+        for (position, spring) in springs.iter().enumerate() {
+            operational_before[position + 1] =
+                operational_before[position] + usize::from(*spring == Spring::Operational);
+        }
+        let mut ways = vec![0; (springs.len() + 1) * groups];
+        ways[springs.len() * groups + row.groups.len()] = 1;
+        for position in (0..springs.len()).rev() {
+            for group in 0..groups {
+                let mut count = 0u128;
+                if springs[position] != Spring::Damaged {
+                    count = ways[(position + 1) * groups + group];
+                }
+                if springs[position] != Spring::Operational && group < row.groups.len() {
+                    // Groups longer than the row may not even fit into a usize
+                    let end = position.saturating_add(row.groups[group]);
+                    let fits = end <= springs.len()
+                        && operational_before[end] == operational_before[position]
+                        && springs.get(end) != Some(&Spring::Damaged);
+                    if fits {
+                        let next = (end + 1).min(springs.len());
+                        count = count.checked_add(ways[next * groups + group + 1])?;
+                    }
+                }
+                ways[position * groups + group] = count;
+            }
+        }
+        Some(Self { row, ways })
+    }
+
+    fn ways(&self, position: usize, group: usize) -> u128 {
+        self.ways[position * (self.row.groups.len() + 1) + group]
+    }
+
+    fn count(&self) -> u128 {
+        self.ways(0, 0)
+    }
+
+    /**
+     * Walks down the table, taking the operational branch while `index` is within its count
+     */
+    fn nth(&self, mut index: u128) -> Option<String> {
+        if index >= self.count() {
+            return None;
+        }
+        let springs = &self.row.springs;
+        let mut arrangement = String::with_capacity(springs.len());
+        let (mut position, mut group) = (0, 0);
+        while position < springs.len() {
+            if springs[position] != Spring::Damaged {
+                let operational = self.ways(position + 1, group);
+                if index < operational {
+                    arrangement.push('.');
+                    position += 1;
+                    continue;
+                }
+                index -= operational;
+            }
+            // The counts guarantee that the group fits here
+            let end = position.saturating_add(self.row.groups[group]);
+            arrangement.extend(std::iter::repeat_n('#', end - position));
+            if end < springs.len() {
+                arrangement.push('.');
+            }
+            position = (end + 1).min(springs.len());
+            group += 1;
+        }
+        Some(arrangement)
+    }
+}
+
+/**
+ * The sum of the arrangements of all rows after unfolding them, counted on all cores. `None`
+ * if it does not fit into a u128
+ */
+pub fn total_arrangements(rows: &[Row], unfold: usize) -> Option<u128> {
+    let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+    let chunk_size = rows.len().div_ceil(threads).max(1);
+    thread::scope(|scope| {
+        let counters: Vec<_> = rows
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk.iter().try_fold(0u128, |total, row| {
+                        total.checked_add(row.unfold(unfold).arrangements()?)
+                    })
+                })
+            })
+            .collect();
+        counters.into_iter().try_fold(0u128, |total, counter| {
+            total.checked_add(counter.join().expect("Counting arrangements panicked")?)
+        })
+    })
+}
+
+/**
+ * `total` as an answer, which only holds up to `i128::MAX`
+ */
+fn answer(total: Option<u128>) -> Result<Answer, NoSolution> {
+    total
+        .filter(|total| i128::try_from(*total).is_ok())
+        .map(Answer::from)
+        .ok_or_else(|| NoSolution("There are too many arrangements to count".to_owned()))
+}
+
+pub struct ConditionRecords {
+    pub rows: Vec<Row>,
+}
+
+impl Solver<'_> for ConditionRecords {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Hot Springs";
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let rows = parse_lines(input, "There are no condition records", Row::parse)?;
+        Ok(Self { rows })
+    }
+
+    fn part_1(&self) -> Result<Answer, NoSolution> {
+        answer(total_arrangements(&self.rows, 1))
+    }
+
+    fn part_2(&self) -> Result<Answer, NoSolution> {
+        answer(total_arrangements(&self.rows, 5))
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::of::<ConditionRecords>());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(line: &str) -> Row {
+        Row::parse(line, 1).unwrap()
+    }
+
+    #[test]
+    fn arrangements_are_counted_per_row() {
+        let counts = [
+            "???.### 1,1,3",
+            ".??..??...?##. 1,1,3",
+            "?###???????? 3,2,1",
+        ]
+        .map(|line| row(line).arrangements());
+        assert_eq!(counts, [Some(1), Some(4), Some(10)]);
+        assert_eq!(
+            row(".??..??...?##. 1,1,3").unfold(5).arrangements(),
+            Some(16384)
+        );
+        assert_eq!(row("# 2").arrangements(), Some(0));
+        assert_eq!(row("?? 18446744073709551615").arrangements(), Some(0));
+        assert_eq!(row("?? 18446744073709551615").arrangement(0), None);
+        assert_eq!(
+            Row::parse("?.# 1,x", 4).unwrap_err().to_string(),
+            "4:5: Expected the sizes of the groups, found \"x\""
+        );
+    }
+
+    #[test]
+    fn arrangements_can_be_listed() {
+        let row = row("?###???????? 3,2,1");
+        let all: Vec<_> = (0..10).filter_map(|index| row.arrangement(index)).collect();
+        assert_eq!(all[0], ".###....##.#");
+        assert_eq!(all[9], ".###.##.#...");
+        assert_eq!(row.arrangement(10), None);
+        for arrangement in &all {
+            let damaged: Vec<_> = arrangement
+                .split('.')
+                .filter(|group| !group.is_empty())
+                .map(str::len)
+                .collect();
+            assert_eq!(damaged, vec![3, 2, 1]);
+        }
+        let sample = row.sample(3);
+        assert_eq!(
+            sample,
+            Some(vec![all[0].clone(), all[3].clone(), all[6].clone()])
+        );
+    }
+
+    #[test]
+    fn too_many_arrangements_have_no_solution() {
+        // Choosing where 50 groups go among 150 gaps is about 2 * 10^40 ways
+        let line = format!("{} {}", "?".repeat(200), vec!["1"; 50].join(","));
+        let records = ConditionRecords::parse(&line).unwrap();
+        assert_eq!(records.rows[0].arrangements(), None);
+        assert_eq!(records.rows[0].sample(3), None);
+        assert_eq!(total_arrangements(&records.rows, 1), None);
+        assert_eq!(
+            records.part_1(),
+            Err(NoSolution(
+                "There are too many arrangements to count".to_owned()
+            ))
+        );
+        // Fits into a u128, but not into an answer
+        assert!(answer(Some(u128::MAX)).is_err());
+        assert_eq!(answer(Some(21)), Ok(Answer::Int(21)));
+    }
+
+    #[test]
+    fn unfolding_joins_copies_with_unknown_springs() {
+        let unfolded = row(".# 1").unfold(3);
+        assert_eq!(
+            unfolded
+                .springs
+                .iter()
+                .map(Spring::to_string)
+                .collect::<String>(),
+            ".#?.#?.#"
+        );
+        assert_eq!(unfolded.groups, vec![1, 1, 1]);
+    }
+}
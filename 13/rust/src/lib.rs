@@ -0,0 +1,252 @@
+use aoc_core::{Answer, Day, Grid, NoSolution, ParseError, Registry, Solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /**
+     * Between two rows
+     */
+    Horizontal,
+    /**
+     * Between two columns
+     */
+    Vertical,
+}
+
+/**
+ * A line of reflection, after `index` rows or columns
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reflection {
+    pub axis: Axis,
+    pub index: usize,
+}
+
+impl Reflection {
+    /**
+     * The columns left of the line, or 100 times the rows above it
+     */
+    pub fn summary(&self) -> usize {
+        match self.axis {
+            Axis::Horizontal => 100 * self.index,
+            Axis::Vertical => self.index,
+        }
+    }
+}
+
+/**
+ * A pattern of ash and rocks, with one bit for each rock in every row and every column
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    pub rows: Vec<u64>,
+    pub columns: Vec<u64>,
+}
+
+impl Pattern {
+    pub const MAX_SIZE: usize = u64::BITS as usize;
+
+    /**
+     * Parses one `block` of the notes, which starts at `first_line` of the input
+     */
+    pub fn parse(block: &str, first_line: usize) -> Result<Self, ParseError> {
+        let grid = Grid::parse_with(block, |char, _| match char {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(ParseError::new(format!(
+                "Expected '#' or '.', found '{char}'"
+            ))),
+        })
+        .map_err(|error| ParseError {
+            line: error.line.map(|line| first_line + line - 1),
+            ..error
+        })?;
+        if grid.width() > Self::MAX_SIZE || grid.height() > Self::MAX_SIZE {
+            return Err(ParseError::new(format!(
+                "Patterns can be at most {} wide and high",
+                Self::MAX_SIZE
+            ))
+            .at_line(first_line));
+        }
+        Ok(Self {
+            rows: grid.rows().map(|row| bits(row.iter())).collect(),
+            columns: grid.columns().map(bits).collect(),
+        })
+    }
+
+    /**
+     * Every line of reflection, where exactly `smudges` cells would have to be fixed for the
+     * pattern to mirror perfectly
+     */
+    pub fn reflections(&self, smudges: u32) -> Vec<Reflection> {
+        let at = |axis| move |index| Reflection { axis, index };
+        mirror_lines(&self.rows, smudges)
+            .map(at(Axis::Horizontal))
+            .chain(mirror_lines(&self.columns, smudges).map(at(Axis::Vertical)))
+            .collect()
+    }
+}
+
+/**
+ * One bit for each rock, the first cell being the lowest
+ */
+fn bits<'a>(cells: impl Iterator<Item = &'a bool>) -> u64 {
+    cells
+        .enumerate()
+        .filter(|(_, rock)| **rock)
+        .fold(0, |bits, (index, _)| bits | 1 << index)
+}
+
+/**
+ * The indices after which `lines` mirror with a Hamming distance of exactly `smudges`
+ */
+fn mirror_lines(lines: &[u64], smudges: u32) -> impl Iterator<Item = usize> + '_ {
+    (1..lines.len()).filter(move |index| {
+        let mut distance = 0;
+        for (before, after) in lines[..*index].iter().rev().zip(&lines[*index..]) {
+            distance += (before ^ after).count_ones();
+            if distance > smudges {
+                return false;
+            }
+        }
+        distance == smudges
+    })
+}
+
+#[derive(Debug)]
+pub struct Notes {
+    pub patterns: Vec<Pattern>,
+}
+
+impl Notes {
+    /**
+     * The one line of reflection of each pattern, with `smudges` fixed
+     */
+    pub fn reflections(&self, smudges: u32) -> Result<Vec<Reflection>, NoSolution> {
+        self.patterns
+            .iter()
+            .enumerate()
+            .map(|(index, pattern)| match pattern.reflections(smudges)[..] {
+                [reflection] => Ok(reflection),
+                ref found => Err(NoSolution(format!(
+                    "Expected one line of reflection in pattern {}, found {}",
+                    index + 1,
+                    found.len()
+                ))),
+            })
+            .collect()
+    }
+
+    pub fn summarize(&self, smudges: u32) -> Result<usize, NoSolution> {
+        Ok(self
+            .reflections(smudges)?
+            .iter()
+            .map(Reflection::summary)
+            .sum())
+    }
+}
+
+impl Solver<'_> for Notes {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Point of Incidence";
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut patterns = vec![];
+        let mut block: Vec<&str> = vec![];
+        let mut first_line = 1;
+        for (index, line) in input.lines().enumerate() {
+            if !line.trim().is_empty() {
+                if block.is_empty() {
+                    first_line = index + 1;
+                }
+                block.push(line);
+            } else if !block.is_empty() {
+                patterns.push(Pattern::parse(&block.join("\n"), first_line)?);
+                block.clear();
+            }
+        }
+        if !block.is_empty() {
+            patterns.push(Pattern::parse(&block.join("\n"), first_line)?);
+        }
+        if patterns.is_empty() {
+            return Err(ParseError::new("There are no patterns"));
+        }
+        Ok(Self { patterns })
+    }
+
+    fn part_1(&self) -> Result<Answer, NoSolution> {
+        self.summarize(0).map(Answer::from)
+    }
+
+    fn part_2(&self) -> Result<Answer, NoSolution> {
+        self.summarize(1).map(Answer::from)
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::of::<Notes>());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/example.txt");
+
+    #[test]
+    fn rows_and_columns_are_bitmasks() {
+        let notes = Notes::parse(EXAMPLE).unwrap();
+        let pattern = &notes.patterns[1];
+        assert_eq!(pattern.rows.len(), 7);
+        assert_eq!(pattern.columns.len(), 9);
+        assert_eq!(pattern.rows[0], 0b1_0011_0001);
+        assert_eq!(pattern.columns[0], 0b101_1011);
+    }
+
+    #[test]
+    fn the_axis_depends_on_the_smudges() {
+        let notes = Notes::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            notes.reflections(0),
+            Ok(vec![
+                Reflection {
+                    axis: Axis::Vertical,
+                    index: 5
+                },
+                Reflection {
+                    axis: Axis::Horizontal,
+                    index: 4
+                }
+            ])
+        );
+        assert_eq!(
+            notes.reflections(1).unwrap()[0],
+            Reflection {
+                axis: Axis::Horizontal,
+                index: 3
+            }
+        );
+        // A pattern that mirrors both ways is ambiguous
+        let symmetric = Notes::parse("##\n##\n").unwrap();
+        assert_eq!(symmetric.patterns[0].reflections(0).len(), 2);
+        assert_eq!(
+            symmetric.summarize(0),
+            Err(NoSolution(
+                "Expected one line of reflection in pattern 1, found 2".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn malformed_patterns_are_located() {
+        let error = |input| Notes::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            error("#.\n..\n\n#.\n#\n"),
+            "5: Expected 2 cells like in the first row, found 1"
+        );
+        assert_eq!(error("#.\n.x\n"), "2:2: Expected '#' or '.', found 'x'");
+        assert_eq!(
+            error(&format!("\n#.\n\n\n{}\n", "#".repeat(65))),
+            "5: Patterns can be at most 64 wide and high"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+/**
+ * The answer to one part of a challenge. Integers are kept as `Int` whenever they fit,
+ * so that equal answers compare equal no matter which type they were created from
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    BigInt(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{value}"),
+            Answer::BigInt(value) => write!(f, "{value}"),
+            Answer::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        match i64::try_from(value) {
+            Ok(value) => Answer::Int(value),
+            Err(_) => Answer::BigInt(value),
+        }
+    }
+}
+
+impl From<u128> for Answer {
+    /**
+     * Panics above `i128::MAX`, which no answer has come close to
+     */
+    fn from(value: u128) -> Self {
+        i128::try_from(value)
+            .expect("Answer does not fit into an i128")
+            .into()
+    }
+}
+
+macro_rules! from_small_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(value: $int) -> Self {
+                    (value as i128).into()
+                }
+            }
+        )*
+    };
+}
+
+from_small_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_are_as_small_as_possible() {
+        assert_eq!(Answer::from(42usize), Answer::Int(42));
+        assert_eq!(Answer::from(42u128), Answer::from(42i8));
+        assert_eq!(Answer::from(-3i64), Answer::Int(-3));
+        assert_eq!(
+            Answer::from(u64::MAX),
+            Answer::BigInt(18_446_744_073_709_551_615)
+        );
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+    }
+}
@@ -0,0 +1,445 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use colored::ColoredString;
+
+use crate::ParseError;
+
+/**
+ * A position in a `Grid`, starting at the top left
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Coord {
+    pub row: usize,
+    pub column: usize,
+}
+
+impl Coord {
+    pub fn new(row: usize, column: usize) -> Self {
+        Self { row, column }
+    }
+}
+
+impl Display for Coord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.row, self.column)
+    }
+}
+
+/**
+ * Up, right, down, left
+ */
+const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/**
+ * Row by row, starting at the top left
+ */
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/**
+ * A rectangle of cells, stored row by row
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl Grid<char> {
+    /**
+     * One row per non-empty line, one cell per character
+     */
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, |char, _| Ok(char))
+    }
+}
+
+impl<T> Grid<T> {
+    /**
+     * Like `parse`, turning every character into a cell with `cell`. All rows must be
+     * equally long
+     */
+    pub fn parse_with(
+        input: &str,
+        mut cell: impl FnMut(char, Coord) -> Result<T, ParseError>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for (line_index, line) in input.lines().enumerate() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+            let row_width = line.chars().count();
+            let expected = *width.get_or_insert(row_width);
+            if row_width != expected {
+                return Err(ParseError::new(format!(
+                    "Expected {expected} cells like in the first row, found {row_width}"
+                ))
+                .at_line(line_index + 1));
+            }
+            for (column, char) in line.chars().enumerate() {
+                cells.push(
+                    cell(char, Coord::new(height, column))
+                        .map_err(|error| error.at(line_index + 1, column + 1))?,
+                );
+            }
+            height += 1;
+        }
+        let Some(width) = width else {
+            return Err(ParseError::new("The grid is empty"));
+        };
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /**
+     * Panics if the rows are not all equally long
+     */
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "All rows of a grid must be equally long"
+        );
+        Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        coord.row < self.height && coord.column < self.width
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.contains(coord)
+            .then(|| &self.cells[coord.row * self.width + coord.column])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.contains(coord)
+            .then(|| &mut self.cells[coord.row * self.width + coord.column])
+    }
+
+    /**
+     * Moves `coord` by the given number of rows and columns, `None` if that leaves the grid
+     */
+    pub fn offset(&self, coord: Coord, rows: isize, columns: isize) -> Option<Coord> {
+        let moved = Coord::new(
+            coord.row.checked_add_signed(rows)?,
+            coord.column.checked_add_signed(columns)?,
+        );
+        self.contains(moved).then_some(moved)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics for a chunk size of 0
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        assert!(column < self.width, "There is no column {column}");
+        self.cells.iter().skip(column).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|column| self.column(column))
+    }
+
+    /**
+     * Row by row
+     */
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |column| Coord::new(row, column)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(&self.cells)
+    }
+
+    pub fn map<U>(&self, mut cell: impl FnMut(Coord, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self
+                .iter()
+                .map(|(coord, value)| cell(coord, value))
+                .collect(),
+        }
+    }
+
+    /**
+     * The neighbours above, right of, below and left of `coord` that are in the grid
+     */
+    pub fn neighbours_4(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        NEIGHBOURS_4
+            .into_iter()
+            .filter_map(move |(rows, columns)| self.offset(coord, rows, columns))
+    }
+
+    /**
+     * Like `neighbours_4`, including the diagonal ones
+     */
+    pub fn neighbours_8(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        NEIGHBOURS_8
+            .into_iter()
+            .filter_map(move |(rows, columns)| self.offset(coord, rows, columns))
+    }
+
+    /**
+     * All cells connected to `start` through 4-neighbours for which `belongs` holds,
+     * including `start`. Row by row, empty if `start` itself does not belong
+     */
+    pub fn flood_fill(
+        &self,
+        start: Coord,
+        mut belongs: impl FnMut(Coord, &T) -> bool,
+    ) -> Vec<Coord> {
+        let mut seen = vec![false; self.cells.len()];
+        let mut region = vec![];
+        let mut todo = vec![start];
+        while let Some(coord) = todo.pop() {
+            let Some(value) = self.get(coord) else {
+                continue;
+            };
+            let index = coord.row * self.width + coord.column;
+            if seen[index] || !belongs(coord, value) {
+                continue;
+            }
+            seen[index] = true;
+            region.push(coord);
+            todo.extend(self.neighbours_4(coord));
+        }
+        region.sort();
+        region
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /**
+     * Rows become columns
+     */
+    pub fn transpose(&self) -> Self {
+        self.rearranged(self.height, self.width, |coord| {
+            Coord::new(coord.column, coord.row)
+        })
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        self.rearranged(self.height, self.width, |coord| {
+            Coord::new(self.height - 1 - coord.column, coord.row)
+        })
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self {
+        self.rearranged(self.height, self.width, |coord| {
+            Coord::new(coord.column, self.width - 1 - coord.row)
+        })
+    }
+
+    /**
+     * Mirrors left and right
+     */
+    pub fn flip_horizontally(&self) -> Self {
+        self.rearranged(self.width, self.height, |coord| {
+            Coord::new(coord.row, self.width - 1 - coord.column)
+        })
+    }
+
+    /**
+     * Mirrors top and bottom
+     */
+    pub fn flip_vertically(&self) -> Self {
+        self.rearranged(self.width, self.height, |coord| {
+            Coord::new(self.height - 1 - coord.row, coord.column)
+        })
+    }
+
+    /**
+     * A `width` by `height` grid, taking each cell from where `source` says in this one
+     */
+    fn rearranged(&self, width: usize, height: usize, source: impl Fn(Coord) -> Coord) -> Self {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |column| Coord::new(row, column)))
+            .map(|coord| self[source(coord)].clone())
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T: Display> Grid<T> {
+    /**
+     * Like `to_string`, letting `style` color or otherwise highlight every cell
+     */
+    pub fn highlighted(
+        &self,
+        mut style: impl FnMut(Coord, &T, ColoredString) -> ColoredString,
+    ) -> String {
+        let mut printed = String::new();
+        for (coord, value) in self.iter() {
+            if coord.column == 0 && coord.row > 0 {
+                printed.push('\n');
+            }
+            let cell = ColoredString::from(value.to_string());
+            printed += &style(coord, value, cell).to_string();
+        }
+        printed
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.highlighted(|_, _, cell| cell))
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    /**
+     * Panics outside of the grid, use `get` to check
+     */
+    fn index(&self, coord: Coord) -> &T {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{coord} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{coord} is outside of the grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use colored::Colorize;
+
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n").unwrap()
+    }
+
+    #[test]
+    fn rows_and_columns_are_views() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+        assert_eq!(grid[Coord::new(1, 0)], 'd');
+        assert_eq!(grid.get(Coord::new(2, 0)), None);
+    }
+
+    #[test]
+    fn grids_can_be_rearranged() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.transpose().transpose(), grid);
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+        assert_eq!(grid.flip_horizontally().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertically().to_string(), "def\nabc");
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbours_4(Coord::new(0, 0)).collect::<Vec<_>>(),
+            vec![Coord::new(0, 1), Coord::new(1, 0)]
+        );
+        assert_eq!(grid.neighbours_8(Coord::new(0, 1)).count(), 5);
+        assert_eq!(grid.neighbours_8(Coord::new(1, 1)).count(), 5);
+        assert_eq!(grid.offset(Coord::new(0, 0), -1, 0), None);
+    }
+
+    #[test]
+    fn flood_fill_finds_connected_regions() {
+        let grid = Grid::parse("..#\n.##\n#..\n").unwrap();
+        let is_dot = |_, cell: &char| *cell == '.';
+        assert_eq!(
+            grid.flood_fill(Coord::new(0, 0), is_dot),
+            vec![Coord::new(0, 0), Coord::new(0, 1), Coord::new(1, 0)]
+        );
+        assert_eq!(grid.flood_fill(Coord::new(2, 2), is_dot).len(), 2);
+        assert!(grid.flood_fill(Coord::new(0, 2), is_dot).is_empty());
+    }
+
+    #[test]
+    fn ragged_or_invalid_grids_are_rejected() {
+        let error = Grid::parse("abc\nde\n").unwrap_err();
+        assert_eq!(error.line, Some(2));
+        let error = Grid::parse_with("12\n3x\n", |char, _| {
+            char.to_digit(10)
+                .ok_or_else(|| ParseError::new(format!("\"{char}\" is no digit")))
+        })
+        .unwrap_err();
+        assert_eq!(error.to_string(), "2:2: \"x\" is no digit");
+    }
+
+    #[test]
+    fn cells_can_be_highlighted() {
+        colored::control::set_override(true);
+        let printed = grid().highlighted(|coord, _, cell| {
+            if coord == Coord::new(1, 1) {
+                cell.red()
+            } else {
+                cell
+            }
+        });
+        assert_eq!(printed, format!("abc\nd{}f", "e".red()));
+    }
+}
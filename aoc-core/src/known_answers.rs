@@ -0,0 +1,204 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{Answer, ParseError, Registry, RunError};
+
+/**
+ * The confirmed answers to all challenge inputs, as written down in `answers.toml`
+ */
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KnownAnswers {
+    #[serde(default, rename = "input")]
+    pub inputs: Vec<KnownInput>,
+}
+
+/**
+ * One challenge input of a day, and the answers to its parts. The `heavy` ones take too
+ * long to be checked every time
+ */
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KnownInput {
+    pub day: u8,
+    pub file: PathBuf,
+    #[serde(default)]
+    pub answers: BTreeMap<usize, Expected>,
+    #[serde(default)]
+    pub heavy: BTreeMap<usize, Expected>,
+}
+
+/**
+ * An answer as written in the file. Numbers too large for TOML's integers can be written
+ * as strings
+ */
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Expected {
+    Int(i64),
+    Text(String),
+}
+
+impl Expected {
+    pub fn matches(&self, answer: &Answer) -> bool {
+        self.to_string() == answer.to_string()
+    }
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expected::Int(value) => write!(f, "{value}"),
+            Expected::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/**
+ * The outcome of solving one part of a known input
+ */
+#[derive(Debug)]
+pub struct Check<'a> {
+    pub input: &'a KnownInput,
+    pub part: usize,
+    pub verdict: Verdict,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        expected: Expected,
+        actual: Answer,
+    },
+    /**
+     * The input could not be read or solved at all
+     */
+    Failed(String),
+}
+
+impl Display for Check<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Day {:02} part {} ({}): ",
+            self.input.day,
+            self.part,
+            self.input.file.display()
+        )?;
+        match &self.verdict {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong { expected, actual } => {
+                write!(f, "expected {expected}, got {actual}")
+            }
+            Verdict::Failed(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl KnownAnswers {
+    pub fn parse(toml: &str) -> Result<Self, ParseError> {
+        toml::from_str(toml).map_err(|error| {
+            let parse_error = ParseError::new(error.message());
+            match error.span() {
+                Some(span) => parse_error.at_offset(toml, span.start),
+                None => parse_error,
+            }
+        })
+    }
+
+    /**
+     * Registered days that no input with answers exists for
+     */
+    pub fn unchecked_days(&self, registry: &Registry) -> Vec<u8> {
+        let known: BTreeSet<_> = self.inputs.iter().map(|input| input.day).collect();
+        registry
+            .days()
+            .map(|day| day.day)
+            .filter(|day| !known.contains(day))
+            .collect()
+    }
+
+    /**
+     * Solves every input found relative to `root` once, including the `heavy` answers
+     * only if asked to. Returns a `Check` for every known answer, in the order of the file
+     */
+    pub fn check(&self, registry: &Registry, root: &Path, heavy: bool) -> Vec<Check<'_>> {
+        self.inputs
+            .iter()
+            .flat_map(|input| {
+                let mut expected = input.answers.clone();
+                if heavy {
+                    expected.extend(input.heavy.clone());
+                }
+                let solved = fs::read_to_string(root.join(&input.file))
+                    .map_err(|error| format!("Could not read the input: {error}"))
+                    .and_then(|challenge| {
+                        let parts: Vec<_> = expected.keys().copied().collect();
+                        registry
+                            .get(input.day)
+                            .ok_or(RunError::UnknownDay(input.day))
+                            .and_then(|day| day.solve(&challenge, &parts))
+                            .map_err(|error| error.to_string())
+                    });
+                expected.into_iter().map(move |(part, expected)| {
+                    let verdict = match &solved {
+                        Err(reason) => Verdict::Failed(reason.clone()),
+                        Ok(answers) => {
+                            let (_, actual) = answers
+                                .iter()
+                                .find(|(solved, _)| *solved == part)
+                                .expect("Every requested part is solved");
+                            if expected.matches(actual) {
+                                Verdict::Correct
+                            } else {
+                                Verdict::Wrong {
+                                    expected,
+                                    actual: actual.clone(),
+                                }
+                            }
+                        }
+                    };
+                    Check {
+                        input,
+                        part,
+                        verdict,
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_are_read_with_their_parts() {
+        let known = KnownAnswers::parse(
+            "[[input]]\nday = 8\nfile = \"08/challenge.txt\"\n\
+             answers = { 1 = 16697, 3 = \"10668805667831\" }\nheavy = { 2 = 10668805667831 }\n",
+        )
+        .unwrap();
+        let input = &known.inputs[0];
+        assert_eq!(input.answers.keys().collect::<Vec<_>>(), vec![&1, &3]);
+        assert!(input.answers[&3].matches(&Answer::Int(10668805667831)));
+        assert!(input.heavy[&2].matches(&Answer::BigInt(10668805667831)));
+        assert!(!input.answers[&1].matches(&Answer::Text("1669".to_owned())));
+    }
+
+    #[test]
+    fn mistakes_in_the_file_are_located() {
+        let error =
+            KnownAnswers::parse("[[input]]\nday = 1\nfile = \"a\"\nanswer = {}\n").unwrap_err();
+        assert_eq!(error.line, Some(4));
+        assert_eq!(error.column, Some(1));
+    }
+}
@@ -0,0 +1,22 @@
+//! Shared plumbing for the solutions of all days.
+//!
+//! Every day is its own crate, which implements `Solver` and exposes a `register` function
+//! adding its `Day` to a `Registry`. The `aoc` binary collects all of them and runs
+//! whichever day and part it is asked for, or checks all of them against the `KnownAnswers`
+//! in `answers.toml`.
+
+mod answer;
+mod grid;
+mod known_answers;
+mod profile;
+mod range_map;
+mod registry;
+mod solver;
+
+pub use answer::Answer;
+pub use grid::{Coord, Grid};
+pub use known_answers::{Check, Expected, KnownAnswers, KnownInput, Verdict};
+pub use profile::{CountingAllocator, Phase, PhaseProfile, Profile};
+pub use range_map::{IntervalSet, RangeMap};
+pub use registry::{Day, Registry, RunError};
+pub use solver::{parse_lines, BorrowingSolver, NoSolution, ParseError, Solver};
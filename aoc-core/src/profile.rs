@@ -0,0 +1,160 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use serde::{Serialize, Serializer};
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/**
+ * The system allocator, counting how many bytes are allocated. A binary that wants memory
+ * in its `Profile` must install it with
+ *
+ * ```
+ * #[global_allocator]
+ * static ALLOCATOR: aoc_core::CountingAllocator = aoc_core::CountingAllocator;
+ * ```
+ */
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    /**
+     * Bytes allocated right now, `None` if this is not the global allocator
+     */
+    pub fn current() -> Option<usize> {
+        Self::installed().then(|| CURRENT.load(Ordering::Relaxed))
+    }
+
+    /**
+     * The most bytes allocated at once since the last `reset_peak`
+     */
+    pub fn peak() -> Option<usize> {
+        Self::installed().then(|| PEAK.load(Ordering::Relaxed))
+    }
+
+    pub fn reset_peak() {
+        PEAK.store(CURRENT.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+
+    /**
+     * Any program allocates before `main`, so a count of 0 means we are not installed
+     */
+    fn installed() -> bool {
+        ALLOCATIONS.load(Ordering::Relaxed) > 0
+    }
+
+    fn allocated(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn deallocated(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+        if !pointer.is_null() {
+            Self::allocated(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc_zeroed(layout);
+        if !pointer.is_null() {
+            Self::allocated(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+        Self::deallocated(layout.size());
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let moved = System.realloc(pointer, layout, new_size);
+        if !moved.is_null() {
+            Self::deallocated(layout.size());
+            Self::allocated(new_size);
+        }
+        moved
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    Parse,
+    Prepare,
+    Part(usize),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Prepare => write!(f, "prepare"),
+            Phase::Part(part) => write!(f, "part {part}"),
+        }
+    }
+}
+
+impl Serialize for Phase {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/**
+ * How long one phase took, and how much memory it used. Memory is only known with the
+ * `CountingAllocator` installed
+ */
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PhaseProfile {
+    pub phase: Phase,
+    pub seconds: f64,
+    /**
+     * The most bytes allocated at once during the phase, including what was allocated before
+     */
+    pub peak_bytes: Option<usize>,
+    /**
+     * How many more bytes are allocated after the phase than before it
+     */
+    pub retained_bytes: Option<isize>,
+}
+
+/**
+ * Collects a `PhaseProfile` for every phase of a run, in the order they ran
+ */
+#[derive(Debug, Default)]
+pub struct Profile {
+    pub phases: Vec<PhaseProfile>,
+}
+
+impl Profile {
+    pub fn measure<T>(&mut self, phase: Phase, run: impl FnOnce() -> T) -> T {
+        let before = CountingAllocator::current();
+        CountingAllocator::reset_peak();
+        let started = Instant::now();
+        let result = run();
+        let seconds = started.elapsed().as_secs_f64();
+        self.phases.push(PhaseProfile {
+            phase,
+            seconds,
+            peak_bytes: CountingAllocator::peak(),
+            retained_bytes: before
+                .zip(CountingAllocator::current())
+                .map(|(before, after)| after as isize - before as isize),
+        });
+        result
+    }
+}
@@ -0,0 +1,305 @@
+use std::ops::Range;
+
+/**
+ * Sorted, non-overlapping half-open intervals
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<Range<i64>>,
+}
+
+impl IntervalSet {
+    /**
+     * Empty intervals are dropped, overlapping and touching ones merged
+     */
+    pub fn new(intervals: impl IntoIterator<Item = Range<i64>>) -> Self {
+        let mut intervals: Vec<_> = intervals
+            .into_iter()
+            .filter(|interval| !interval.is_empty())
+            .collect();
+        intervals.sort_by_key(|interval| interval.start);
+        let mut merged: Vec<Range<i64>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        Self { intervals: merged }
+    }
+
+    pub fn intervals(&self) -> &[Range<i64>] {
+        &self.intervals
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    /**
+     * How many values are in the set
+     */
+    pub fn len(&self) -> u64 {
+        self.intervals
+            .iter()
+            .map(|interval| interval.end.abs_diff(interval.start))
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+}
+
+/**
+ * Values in `start..end` are moved by `offset`. It is wider than the values, as a piece may
+ * move values from one end of the i64s to the other. Where they land still fits an i64
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Piece {
+    start: i64,
+    end: i64,
+    offset: i128,
+}
+
+/**
+ * `value` moved by `offset`, which must land within the i64s
+ */
+fn moved(value: i64, offset: i128) -> i64 {
+    i64::try_from(i128::from(value) + offset).expect("Pieces move values to other i64s")
+}
+
+/**
+ * A piecewise mapping of integers, that moves whole ranges of them by an offset each.
+ * Values outside of all ranges map to themselves
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeMap {
+    /**
+     * Sorted, non-overlapping and never with an offset of 0
+     */
+    pieces: Vec<Piece>,
+}
+
+impl RangeMap {
+    /**
+     * Maps `source..source + length` to `destination..destination + length` for every entry.
+     * Fails if an entry fails `check_entry`, or if the sources of two entries overlap
+     */
+    pub fn new(entries: impl IntoIterator<Item = (i64, i64, i64)>) -> Result<Self, String> {
+        let mut pieces = entries
+            .into_iter()
+            .map(|(destination, source, length)| {
+                Self::check_entry(destination, source, length)?;
+                Ok(Piece {
+                    start: source,
+                    end: source + length,
+                    offset: i128::from(destination) - i128::from(source),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        pieces.retain(|piece| piece.start < piece.end);
+        pieces.sort_by_key(|piece| piece.start);
+        if let Some(overlap) = pieces.windows(2).find(|pair| pair[0].end > pair[1].start) {
+            return Err(format!(
+                "{}..{} overlaps with {}..{}",
+                overlap[0].start, overlap[0].end, overlap[1].start, overlap[1].end
+            ));
+        }
+        Ok(Self::from_pieces(pieces))
+    }
+
+    /**
+     * Why `new` refuses an entry on its own: its length is negative, or one of its ranges
+     * does not fit into an i64
+     */
+    pub fn check_entry(destination: i64, source: i64, length: i64) -> Result<(), String> {
+        if length < 0 {
+            return Err(format!("The length {length} is negative"));
+        }
+        if source.checked_add(length).is_none() || destination.checked_add(length).is_none() {
+            return Err(format!(
+                "The ranges of {destination} {source} {length} do not fit into 64 bits"
+            ));
+        }
+        Ok(())
+    }
+
+    /**
+     * Drops pieces that do not move anything and merges neighbours that move by the same
+     * offset. `pieces` must be sorted and non-overlapping
+     */
+    fn from_pieces(pieces: Vec<Piece>) -> Self {
+        let mut merged: Vec<Piece> = Vec::with_capacity(pieces.len());
+        for piece in pieces.into_iter().filter(|piece| piece.offset != 0) {
+            match merged.last_mut() {
+                Some(last) if last.end == piece.start && last.offset == piece.offset => {
+                    last.end = piece.end
+                }
+                _ => merged.push(piece),
+            }
+        }
+        Self { pieces: merged }
+    }
+
+    pub fn apply(&self, value: i64) -> i64 {
+        let index = self.pieces.partition_point(|piece| piece.end <= value);
+        match self.pieces.get(index) {
+            Some(piece) if piece.start <= value => moved(value, piece.offset),
+            _ => value,
+        }
+    }
+
+    /**
+     * Cuts `start..end` where the pieces begin and end, and tells the offset of every cut
+     */
+    fn split(&self, start: i64, end: i64) -> Vec<Piece> {
+        let mut split = vec![];
+        let mut at = start;
+        let first = self.pieces.partition_point(|piece| piece.end <= start);
+        for piece in &self.pieces[first..] {
+            if piece.start >= end {
+                break;
+            }
+            if at < piece.start {
+                split.push(Piece {
+                    start: at,
+                    end: piece.start,
+                    offset: 0,
+                });
+            }
+            let piece_end = piece.end.min(end);
+            split.push(Piece {
+                start: at.max(piece.start),
+                end: piece_end,
+                offset: piece.offset,
+            });
+            at = piece_end;
+        }
+        if at < end {
+            split.push(Piece {
+                start: at,
+                end,
+                offset: 0,
+            });
+        }
+        split
+    }
+
+    /**
+     * Maps every value of every interval at once
+     */
+    pub fn apply_intervals(&self, intervals: &IntervalSet) -> IntervalSet {
+        IntervalSet::new(intervals.intervals().iter().flat_map(|interval| {
+            self.split(interval.start, interval.end)
+                .into_iter()
+                .map(|piece| moved(piece.start, piece.offset)..moved(piece.end, piece.offset))
+        }))
+    }
+
+    /**
+     * One map that does what applying `self` and then `then` does
+     */
+    pub fn compose(&self, then: &RangeMap) -> RangeMap {
+        // Every piece of `self` is cut into pieces of its own source range, so they stay sorted.
+        // `i64::MAX` is left out, but no piece can contain it, as their ends are exclusive.
+        // So both maps leave it where it is, and so does the composed one
+        let pieces = self
+            .split(i64::MIN, i64::MAX)
+            .into_iter()
+            .flat_map(|piece| {
+                then.split(
+                    moved(piece.start, piece.offset),
+                    moved(piece.end, piece.offset),
+                )
+                .into_iter()
+                .map(move |then_piece| Piece {
+                    start: moved(then_piece.start, -piece.offset),
+                    end: moved(then_piece.end, -piece.offset),
+                    offset: piece.offset + then_piece.offset,
+                })
+            })
+            .collect();
+        Self::from_pieces(pieces)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seed_to_soil() -> RangeMap {
+        RangeMap::new([(50, 98, 2), (52, 50, 48)]).unwrap()
+    }
+
+    fn soil_to_fertilizer() -> RangeMap {
+        RangeMap::new([(0, 15, 37), (37, 52, 2), (39, 0, 15)]).unwrap()
+    }
+
+    #[test]
+    fn values_are_moved_by_their_range() {
+        let map = seed_to_soil();
+        assert_eq!(
+            [0, 49, 50, 79, 97, 98, 99, 100].map(|value| map.apply(value)),
+            [0, 49, 52, 81, 99, 50, 51, 100]
+        );
+        assert!(RangeMap::new([(0, 0, 10), (20, 5, 10)]).is_err());
+    }
+
+    #[test]
+    fn entries_must_fit_into_64_bits() {
+        assert_eq!(
+            RangeMap::new([(0, 10, -5)]),
+            Err("The length -5 is negative".to_owned())
+        );
+        assert!(RangeMap::new([(0, i64::MAX, 1)]).is_err());
+        assert!(RangeMap::new([(i64::MAX, 0, 1)]).is_err());
+        let last = RangeMap::new([(0, i64::MAX - 1, 1)]).unwrap();
+        assert_eq!(last.apply(i64::MAX - 1), 0);
+
+        let composed = last.compose(&RangeMap::new([(i64::MIN, 0, 1)]).unwrap());
+        assert_eq!(composed.apply(i64::MAX - 1), i64::MIN);
+        assert_eq!(composed.apply(i64::MAX), i64::MAX);
+        assert_eq!(composed.apply(i64::MIN), i64::MIN);
+        // Moves almost all the way across the i64s
+        let across = RangeMap::new([(i64::MAX - 1, i64::MIN, 1)]).unwrap();
+        assert_eq!(across.apply(i64::MIN), i64::MAX - 1);
+        assert_eq!(
+            across
+                .apply_intervals(&IntervalSet::new([
+                    i64::MIN..i64::MIN + 1,
+                    i64::MIN + 1..i64::MIN + 2
+                ]))
+                .intervals(),
+            &[i64::MIN + 1..i64::MIN + 2, i64::MAX - 1..i64::MAX]
+        );
+    }
+
+    #[test]
+    fn composing_is_applying_one_after_the_other() {
+        let composed = seed_to_soil().compose(&soil_to_fertilizer());
+        for value in -5..120 {
+            assert_eq!(
+                composed.apply(value),
+                soil_to_fertilizer().apply(seed_to_soil().apply(value)),
+                "{value}"
+            );
+        }
+        assert_eq!(RangeMap::default().compose(&seed_to_soil()), seed_to_soil());
+    }
+
+    #[test]
+    fn intervals_are_mapped_at_once() {
+        let seeds = IntervalSet::new([79..93, 55..68]);
+        let soil = seed_to_soil().apply_intervals(&seeds);
+        assert_eq!(soil.intervals(), &[57..70, 81..95]);
+        assert_eq!(soil.len(), seeds.len());
+
+        // 95..98 moves up to 97..100 and 98..100 down to 50..52, while 100 stays
+        let crossing = IntervalSet::new(vec![95..98, 98..101]);
+        assert_eq!(
+            seed_to_soil().apply_intervals(&crossing).intervals(),
+            &[50..52, 97..101]
+        );
+    }
+}
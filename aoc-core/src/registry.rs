@@ -0,0 +1,370 @@
+use std::{any::type_name, collections::BTreeMap, error::Error, fmt::Display, marker::PhantomData};
+
+use crate::{Answer, BorrowingSolver, NoSolution, ParseError, Phase, Profile, Solver};
+
+type SolveParts = fn(&str, &[usize], &mut Profile) -> Result<Vec<(usize, Answer)>, RunError>;
+
+/**
+ * A registered solver, with its type erased so that all days fit into one `Registry`
+ */
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    pub title: &'static str,
+    pub extra_parts: &'static [&'static str],
+    /**
+     * The name of the solver's type, which tells several solvers of the same day apart
+     */
+    pub backend: &'static str,
+    solve: SolveParts,
+}
+
+/**
+ * A solver that owns everything it needs, for every lifetime of the input
+ */
+struct Owned<S>(PhantomData<S>);
+
+impl<S: for<'a> Solver<'a>> BorrowingSolver for Owned<S> {
+    type Solver<'a> = S;
+}
+
+impl Day {
+    pub fn of<S: for<'a> Solver<'a>>() -> Self {
+        Self::borrowing::<Owned<S>>()
+    }
+
+    pub fn borrowing<B: BorrowingSolver>() -> Self {
+        type Static<B> = <B as BorrowingSolver>::Solver<'static>;
+        let path = type_name::<Static<B>>();
+        let path = path.split('<').next().unwrap_or(path);
+        Self {
+            day: Static::<B>::DAY,
+            title: Static::<B>::TITLE,
+            extra_parts: Static::<B>::EXTRA_PARTS,
+            backend: path.rsplit("::").next().unwrap_or(path),
+            solve: solve_parts::<B>,
+        }
+    }
+
+    pub fn parts(&self) -> usize {
+        2 + self.extra_parts.len()
+    }
+
+    /**
+     * Parses and prepares `input` once, then solves all `parts`
+     */
+    pub fn solve(&self, input: &str, parts: &[usize]) -> Result<Vec<(usize, Answer)>, RunError> {
+        self.solve_profiled(input, parts, &mut Profile::default())
+    }
+
+    /**
+     * Like `solve`, measuring every phase into `profile`
+     */
+    pub fn solve_profiled(
+        &self,
+        input: &str,
+        parts: &[usize],
+        profile: &mut Profile,
+    ) -> Result<Vec<(usize, Answer)>, RunError> {
+        (self.solve)(input, parts, profile)
+    }
+}
+
+fn solve_parts<B: BorrowingSolver>(
+    input: &str,
+    parts: &[usize],
+    profile: &mut Profile,
+) -> Result<Vec<(usize, Answer)>, RunError> {
+    let mut solver = profile
+        .measure(Phase::Parse, || B::Solver::parse(input))
+        .map_err(RunError::Parse)?;
+    profile.measure(Phase::Prepare, || solver.prepare());
+    parts
+        .iter()
+        .map(
+            |part| match profile.measure(Phase::Part(*part), || solver.solve(*part)) {
+                Some(Ok(answer)) => Ok((*part, answer)),
+                Some(Err(reason)) => Err(RunError::NoSolution {
+                    part: *part,
+                    reason,
+                }),
+                None => Err(RunError::UnknownPart {
+                    day: B::Solver::DAY,
+                    part: *part,
+                    parts: B::Solver::parts(),
+                }),
+            },
+        )
+        .collect()
+}
+
+#[derive(Default)]
+pub struct Registry {
+    /**
+     * Every solver of a day, the one that runs by default first
+     */
+    days: BTreeMap<u8, Vec<Day>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RunError {
+    UnknownDay(u8),
+    UnknownPart { day: u8, part: usize, parts: usize },
+    Parse(ParseError),
+    NoSolution { part: usize, reason: NoSolution },
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunError::UnknownDay(day) => write!(f, "Day {day} has no solution (yet)"),
+            RunError::UnknownPart { day, part, parts } => {
+                write!(f, "Day {day} has no part {part}, only parts 1 to {parts}")
+            }
+            RunError::Parse(error) => write!(f, "Could not parse the input: {error}"),
+            RunError::NoSolution { part, reason } => {
+                write!(f, "Part {part} has no solution: {reason}")
+            }
+        }
+    }
+}
+
+impl Error for RunError {}
+
+impl Registry {
+    /**
+     * Panics if the day was registered before, as that is a mistake in the runner
+     */
+    pub fn register(&mut self, day: Day) {
+        let number = day.day;
+        if self.days.insert(number, vec![day]).is_some() {
+            panic!("Day {number} was registered twice");
+        }
+    }
+
+    /**
+     * Adds another solver for a day that was registered before, which only runs when asked
+     * for by `backends`. Panics if the day is not registered yet, or already has a solver of
+     * the same type
+     */
+    pub fn register_backend(&mut self, day: Day) {
+        let number = day.day;
+        let backends = self
+            .days
+            .get_mut(&number)
+            .unwrap_or_else(|| panic!("Day {number} must be registered before its backends"));
+        if backends.iter().any(|other| other.backend == day.backend) {
+            panic!("{} was registered twice for day {number}", day.backend);
+        }
+        backends.push(day);
+    }
+
+    /**
+     * The solver that runs by default
+     */
+    pub fn get(&self, day: u8) -> Option<&Day> {
+        self.days.get(&day).map(|backends| &backends[0])
+    }
+
+    /**
+     * Every solver of `day`, the default one first
+     */
+    pub fn backends(&self, day: u8) -> &[Day] {
+        self.days.get(&day).map_or(&[], Vec::as_slice)
+    }
+
+    /**
+     * In order of the day
+     */
+    pub fn days(&self) -> impl Iterator<Item = &Day> {
+        self.days.values().map(|backends| &backends[0])
+    }
+
+    /**
+     * Solves `part` of `day`, starting at 1, or all parts if `part` is `None`. Returns the
+     * answers together with the part they belong to
+     */
+    pub fn run(
+        &self,
+        day: u8,
+        part: Option<usize>,
+        input: &str,
+    ) -> Result<Vec<(usize, Answer)>, RunError> {
+        self.run_profiled(day, part, input, &mut Profile::default())
+    }
+
+    /**
+     * Like `run`, measuring every phase into `profile`
+     */
+    pub fn run_profiled(
+        &self,
+        day: u8,
+        part: Option<usize>,
+        input: &str,
+        profile: &mut Profile,
+    ) -> Result<Vec<(usize, Answer)>, RunError> {
+        let registered = self.get(day).ok_or(RunError::UnknownDay(day))?;
+        let parts = match part {
+            None => (1..=registered.parts()).collect(),
+            Some(part) => vec![part],
+        };
+        registered.solve_profiled(input, &parts, profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts the characters of its input, and shouts it
+    struct Shout(String);
+
+    impl Solver<'_> for Shout {
+        const DAY: u8 = 3;
+        const TITLE: &'static str = "Shout";
+        const EXTRA_PARTS: &'static [&'static str] = &["Whisper"];
+
+        fn parse(input: &str) -> Result<Self, ParseError> {
+            if input.is_empty() {
+                return Err(ParseError::new("Nothing to shout").at_line(1));
+            }
+            Ok(Shout(input.to_owned()))
+        }
+
+        fn part_1(&self) -> Result<Answer, NoSolution> {
+            Ok(self.0.len().into())
+        }
+
+        fn part_2(&self) -> Result<Answer, NoSolution> {
+            Ok(self.0.to_uppercase().into())
+        }
+
+        fn extra_part(&self, _part: usize) -> Result<Answer, NoSolution> {
+            Err(NoSolution("Can not whisper".to_owned()))
+        }
+    }
+
+    /// Shouts like `Shout`, but borrows its input
+    struct Echo<'a>(&'a str);
+
+    impl<'a> Solver<'a> for Echo<'a> {
+        const DAY: u8 = 3;
+        const TITLE: &'static str = "Shout";
+
+        fn parse(input: &'a str) -> Result<Self, ParseError> {
+            Ok(Echo(input))
+        }
+
+        fn part_1(&self) -> Result<Answer, NoSolution> {
+            Ok(self.0.len().into())
+        }
+
+        fn part_2(&self) -> Result<Answer, NoSolution> {
+            Ok(self.0.to_uppercase().into())
+        }
+    }
+
+    struct BorrowingEcho;
+
+    impl BorrowingSolver for BorrowingEcho {
+        type Solver<'a> = Echo<'a>;
+    }
+
+    fn registry() -> Registry {
+        let mut registry = Registry::default();
+        registry.register(Day::of::<Shout>());
+        registry
+    }
+
+    #[test]
+    fn runs_one_or_all_parts() {
+        assert_eq!(
+            registry().run(3, Some(2), "abc"),
+            Ok(vec![(2, Answer::Text("ABC".to_owned()))])
+        );
+        assert_eq!(
+            registry().run(3, Some(3), "abc"),
+            Err(RunError::NoSolution {
+                part: 3,
+                reason: NoSolution("Can not whisper".to_owned())
+            })
+        );
+        assert_eq!(
+            registry().run(3, None, "abc").unwrap_err().to_string(),
+            "Part 3 has no solution: Can not whisper"
+        );
+    }
+
+    #[test]
+    fn unknown_days_and_parts_are_errors() {
+        assert_eq!(registry().run(4, None, ""), Err(RunError::UnknownDay(4)));
+        assert_eq!(
+            registry().run(3, Some(4), "abc"),
+            Err(RunError::UnknownPart {
+                day: 3,
+                part: 4,
+                parts: 3
+            })
+        );
+        assert_eq!(
+            registry().run(3, Some(0), "").unwrap_err().to_string(),
+            "Could not parse the input: 1: Nothing to shout"
+        );
+    }
+
+    #[test]
+    fn every_phase_is_profiled() {
+        let mut profile = Profile::default();
+        registry()
+            .run_profiled(3, Some(2), "abc", &mut profile)
+            .unwrap();
+        assert_eq!(
+            profile
+                .phases
+                .iter()
+                .map(|phase| phase.phase)
+                .collect::<Vec<_>>(),
+            vec![Phase::Parse, Phase::Prepare, Phase::Part(2)]
+        );
+        // Tests run with the system allocator
+        assert_eq!(profile.phases[0].peak_bytes, None);
+    }
+
+    #[test]
+    fn days_can_have_several_backends() {
+        let mut registry = registry();
+        registry.register_backend(Day::borrowing::<BorrowingEcho>());
+        let backends = registry.backends(3);
+        assert_eq!(
+            backends.iter().map(|day| day.backend).collect::<Vec<_>>(),
+            vec!["Shout", "Echo"]
+        );
+        assert_eq!(registry.get(3).unwrap().backend, "Shout");
+        assert_eq!(registry.days().count(), 1);
+        assert_eq!(
+            backends[1].solve("abc", &[2]),
+            Ok(vec![(2, Answer::Text("ABC".to_owned()))])
+        );
+        assert_eq!(
+            backends[1].solve("abc", &[3]),
+            Err(RunError::UnknownPart {
+                day: 3,
+                part: 3,
+                parts: 2
+            })
+        );
+        assert!(registry.backends(4).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Shout was registered twice for day 3")]
+    fn backends_can_only_be_registered_once() {
+        registry().register_backend(Day::of::<Shout>());
+    }
+
+    #[test]
+    #[should_panic(expected = "registered twice")]
+    fn days_can_only_be_registered_once() {
+        registry().register(Day::of::<Shout>());
+    }
+}
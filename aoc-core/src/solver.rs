@@ -0,0 +1,181 @@
+use std::{error::Error, fmt::Display};
+
+use crate::Answer;
+
+/**
+ * Why a challenge input could not be parsed. Lines and columns start at 1
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            line: None,
+            column: None,
+        }
+    }
+
+    pub fn at_line(self, line: usize) -> Self {
+        Self {
+            line: Some(line),
+            ..self
+        }
+    }
+
+    pub fn at(self, line: usize, column: usize) -> Self {
+        Self {
+            line: Some(line),
+            column: Some(column),
+            ..self
+        }
+    }
+
+    /**
+     * Locates the error at the byte `offset` into `text`
+     */
+    pub fn at_offset(self, text: &str, offset: usize) -> Self {
+        let before = &text[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before[before.rfind('\n').map_or(0, |at| at + 1)..]
+            .chars()
+            .count()
+            + 1;
+        self.at(line, column)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "{line}:{column}: {}", self.message),
+            (Some(line), None) => write!(f, "{line}: {}", self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/**
+ * Parses every line of `input` that is not blank with `parse`. It gets the line without its
+ * trailing whitespace, and the line's number to locate errors with. Fails with `empty` if
+ * there is no such line
+ */
+pub fn parse_lines<T>(
+    input: &str,
+    empty: &str,
+    mut parse: impl FnMut(&str, usize) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    let parsed = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| parse(line.trim_end(), index + 1))
+        .collect::<Result<Vec<_>, _>>()?;
+    if parsed.is_empty() {
+        return Err(ParseError::new(empty));
+    }
+    Ok(parsed)
+}
+
+/**
+ * The input parsed fine, but the question it asks has no answer
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoSolution(pub String);
+
+impl Display for NoSolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for NoSolution {}
+
+/**
+ * A solution to one day's challenge. Parse the input once, then ask for as many parts
+ * as you like.
+ *
+ * Solvers that borrow from the input can implement this too. They are registered through
+ * a `BorrowingSolver`
+ */
+pub trait Solver<'a>: Sized {
+    const DAY: u8;
+    const TITLE: &'static str;
+    /**
+     * What the parts beyond the two of the puzzle compute. They are numbered on from 3
+     */
+    const EXTRA_PARTS: &'static [&'static str] = &[];
+
+    fn parse(input: &'a str) -> Result<Self, ParseError>;
+
+    /**
+     * Precomputation that all parts share. The runner calls it after parsing, so that it
+     * can be timed on its own. Solvers must also work if it is never called
+     */
+    fn prepare(&mut self) {}
+
+    fn part_1(&self) -> Result<Answer, NoSolution>;
+
+    fn part_2(&self) -> Result<Answer, NoSolution>;
+
+    /**
+     * Only called for the parts declared in `EXTRA_PARTS`
+     */
+    fn extra_part(&self, part: usize) -> Result<Answer, NoSolution> {
+        unreachable!("{} declares no part {part}", Self::TITLE)
+    }
+
+    fn parts() -> usize {
+        2 + Self::EXTRA_PARTS.len()
+    }
+
+    /**
+     * `None` if there is no such part
+     */
+    fn solve(&self, part: usize) -> Option<Result<Answer, NoSolution>> {
+        match part {
+            1 => Some(self.part_1()),
+            2 => Some(self.part_2()),
+            _ if (3..=Self::parts()).contains(&part) => Some(self.extra_part(part)),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * Names a solver that borrows from its input, like `Walker<'a>`, for every lifetime of the
+ * input, so that `Day::borrowing` can register it
+ */
+pub trait BorrowingSolver {
+    type Solver<'a>: Solver<'a>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_lines_are_skipped_but_counted() {
+        let parse = |input| {
+            parse_lines(input, "Nothing here", |line, line_number| {
+                line.parse::<u8>().map_err(|_| {
+                    ParseError::new(format!("Expected a number, found \"{line}\""))
+                        .at_line(line_number)
+                })
+            })
+        };
+        assert_eq!(parse("1\n\n  \n2  \n"), Ok(vec![1, 2]));
+        assert_eq!(
+            parse("1\n\nx\n").unwrap_err().to_string(),
+            "3: Expected a number, found \"x\""
+        );
+        assert_eq!(parse("\n \n"), Err(ParseError::new("Nothing here")));
+    }
+}
@@ -0,0 +1,50 @@
+//! Generates a test for every example input in the `examples` folder of a day, see
+//! `tests/examples.rs`
+
+use std::{env, fs, path::Path};
+
+fn main() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let mut days: Vec<_> = fs::read_dir(root)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|name| name.len() == 2 && name.chars().all(|char| char.is_ascii_digit()))
+        .collect();
+    days.sort();
+
+    let mut tests = String::new();
+    for day in days {
+        // A new `examples` folder changes the day's folder
+        println!("cargo:rerun-if-changed={}", root.join(&day).display());
+        let examples = root.join(&day).join("examples");
+        if !examples.is_dir() {
+            continue;
+        }
+        println!("cargo:rerun-if-changed={}", examples.display());
+        let mut files: Vec<_> = fs::read_dir(&examples)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .filter(|name| name.ends_with(".txt"))
+            .collect();
+        files.sort();
+        for file in files {
+            let name: String = file
+                .trim_end_matches(".txt")
+                .chars()
+                .map(|char| {
+                    if char.is_ascii_alphanumeric() {
+                        char.to_ascii_lowercase()
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            tests += &format!(
+                "#[test]\nfn day_{day}_{name}() {{\n    check_example({day:?}, {file:?});\n}}\n\n"
+            );
+        }
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out, tests).unwrap();
+}
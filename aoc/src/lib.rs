@@ -0,0 +1,22 @@
+use aoc_core::Registry;
+
+/**
+ * Every day that should be runnable must be added here
+ */
+pub fn registry() -> Registry {
+    let mut registry = Registry::default();
+    advent_of_code_23_01::register(&mut registry);
+    advent_of_code_23_02::register(&mut registry);
+    advent_of_code_23_03::register(&mut registry);
+    advent_of_code_23_04::register(&mut registry);
+    advent_of_code_23_05::register(&mut registry);
+    advent_of_code_23_06::register(&mut registry);
+    advent_of_code_23_07::register(&mut registry);
+    advent_of_code_23_08::register(&mut registry);
+    advent_of_code_23_09::register(&mut registry);
+    advent_of_code_23_10::register(&mut registry);
+    advent_of_code_23_11::register(&mut registry);
+    advent_of_code_23_12::register(&mut registry);
+    advent_of_code_23_13::register(&mut registry);
+    registry
+}
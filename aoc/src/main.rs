@@ -0,0 +1,236 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use aoc::registry;
+use aoc_core::{Answer, CountingAllocator, KnownAnswers, PhaseProfile, Profile, Verdict};
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Run the Advent of Code 2023 solutions of any day
+#[derive(Parser)]
+#[command(
+    version,
+    about,
+    after_help = "Exit codes:
+  0  Success
+  1  The input could not be read, the day or part has no solution, or a known answer
+     was not found
+  2  Invalid arguments"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a day's challenge
+    Run {
+        /// The day, from 1 to 25
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Only solve this part, instead of all
+        #[arg(long)]
+        part: Option<usize>,
+
+        /// The challenge input
+        input: PathBuf,
+    },
+    /// Solve a day's challenge, measuring the time and memory of every phase
+    Profile {
+        /// The day, from 1 to 25
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Only solve this part, instead of all
+        #[arg(long)]
+        part: Option<usize>,
+
+        /// Print the answers and measurements as JSON, instead of a table
+        #[arg(long)]
+        json: bool,
+
+        /// The challenge input
+        input: PathBuf,
+    },
+    /// List the days that can be run
+    List,
+    /// Solve every input with known answers, and report the ones that are not found
+    Check {
+        /// The known answers. Their inputs are looked up relative to this file
+        #[arg(long, default_value = "answers.toml")]
+        answers: PathBuf,
+
+        /// Also check the answers that take long to find
+        #[arg(long)]
+        heavy: bool,
+    },
+}
+
+#[derive(Serialize)]
+struct ProfileReport {
+    day: u8,
+    answers: Vec<PartAnswer>,
+    phases: Vec<PhaseProfile>,
+}
+
+#[derive(Serialize)]
+struct PartAnswer {
+    part: usize,
+    answer: String,
+}
+
+fn read_input(input: &Path) -> Result<String, ExitCode> {
+    fs::read_to_string(input).map_err(|error| {
+        eprintln!("Could not read {}: {error}", input.display());
+        ExitCode::FAILURE
+    })
+}
+
+fn format_bytes(bytes: Option<isize>) -> String {
+    let Some(bytes) = bytes else {
+        return "?".to_owned();
+    };
+    let mut size = bytes as f64;
+    for unit in ["B", "KiB", "MiB"] {
+        if size.abs() < 1024.0 {
+            return format!("{size:.1} {unit}");
+        }
+        size /= 1024.0;
+    }
+    format!("{size:.1} GiB")
+}
+
+fn print_profile(answers: &[(usize, Answer)], phases: &[PhaseProfile]) {
+    for (part, answer) in answers {
+        println!("Part {part}'s result is {answer}");
+    }
+    println!();
+    println!(
+        "{:<10} {:>12} {:>12} {:>12}",
+        "Phase", "Time", "Peak memory", "Retained"
+    );
+    for phase in phases {
+        println!(
+            "{:<10} {:>9.3} ms {:>12} {:>12}",
+            phase.phase.to_string(),
+            phase.seconds * 1000.0,
+            format_bytes(phase.peak_bytes.map(|bytes| bytes as isize)),
+            format_bytes(phase.retained_bytes),
+        );
+    }
+}
+
+fn main() -> ExitCode {
+    let registry = registry();
+    match Cli::parse().command {
+        Command::Run { day, part, input } => {
+            let challenge = match read_input(&input) {
+                Ok(challenge) => challenge,
+                Err(code) => return code,
+            };
+            match registry.run(day, part, &challenge) {
+                Ok(answers) => {
+                    for (part, answer) in answers {
+                        println!("Part {part}'s result is {answer}");
+                    }
+                    ExitCode::SUCCESS
+                }
+                Err(error) => {
+                    eprintln!("{error}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Profile {
+            day,
+            part,
+            json,
+            input,
+        } => {
+            let challenge = match read_input(&input) {
+                Ok(challenge) => challenge,
+                Err(code) => return code,
+            };
+            let mut profile = Profile::default();
+            let answers = match registry.run_profiled(day, part, &challenge, &mut profile) {
+                Ok(answers) => answers,
+                Err(error) => {
+                    eprintln!("{error}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            if json {
+                let report = ProfileReport {
+                    day,
+                    answers: answers
+                        .into_iter()
+                        .map(|(part, answer)| PartAnswer {
+                            part,
+                            answer: answer.to_string(),
+                        })
+                        .collect(),
+                    phases: profile.phases,
+                };
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&report).expect("Reports are always serializable")
+                );
+            } else {
+                print_profile(&answers, &profile.phases);
+            }
+            ExitCode::SUCCESS
+        }
+        Command::List => {
+            for day in registry.days() {
+                println!("Day {:02}: {}", day.day, day.title);
+                for (part, description) in (3..).zip(day.extra_parts) {
+                    println!("  Part {part}: {description}");
+                }
+                let backends = registry.backends(day.day);
+                if backends.len() > 1 {
+                    let names: Vec<_> = backends.iter().map(|backend| backend.backend).collect();
+                    println!("  Backends: {}", names.join(", "));
+                }
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Check { answers, heavy } => {
+            let known = match read_input(&answers) {
+                Ok(known) => known,
+                Err(code) => return code,
+            };
+            let known = match KnownAnswers::parse(&known) {
+                Ok(known) => known,
+                Err(error) => {
+                    eprintln!("{}:{error}", answers.display());
+                    return ExitCode::FAILURE;
+                }
+            };
+            for day in known.unchecked_days(&registry) {
+                println!("Day {day:02} has no known answers");
+            }
+            let root = answers.parent().unwrap_or(Path::new("."));
+            let mut mismatches = 0;
+            for check in known.check(&registry, root, heavy) {
+                if check.verdict != Verdict::Correct {
+                    mismatches += 1;
+                }
+                println!("{check}");
+            }
+            if mismatches == 0 {
+                ExitCode::SUCCESS
+            } else {
+                eprintln!("{mismatches} known answers were not found");
+                ExitCode::FAILURE
+            }
+        }
+    }
+}
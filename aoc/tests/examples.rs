@@ -0,0 +1,49 @@
+use std::{fs, path::Path};
+
+use aoc_core::{KnownAnswers, Registry, Verdict};
+
+/**
+ * Solves `file` in the `examples` folder of `day` with every backend of the day, and compares
+ * the answers to the ones in that folder's `answers.toml`
+ */
+fn check_example(day: &str, file: &str) {
+    let examples = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(day)
+        .join("examples");
+    let answers = examples.join("answers.toml");
+    let known = KnownAnswers::parse(&fs::read_to_string(&answers).unwrap())
+        .unwrap_or_else(|error| panic!("{}:{error}", answers.display()));
+    let input = known
+        .inputs
+        .into_iter()
+        .find(|input| input.file == Path::new(file))
+        .unwrap_or_else(|| panic!("{} has no answers for {file}", answers.display()));
+    assert!(
+        !input.answers.is_empty() || !input.heavy.is_empty(),
+        "{} has no answers for {file}",
+        answers.display()
+    );
+
+    let example = KnownAnswers {
+        inputs: vec![input],
+    };
+    let backends = aoc::registry().backends(example.inputs[0].day).to_vec();
+    assert!(!backends.is_empty(), "Day {day} is not registered");
+    let mismatches: Vec<_> = backends
+        .into_iter()
+        .flat_map(|backend| {
+            let mut registry = Registry::default();
+            registry.register(backend);
+            example
+                .check(&registry, &examples, true)
+                .iter()
+                .filter(|check| check.verdict != Verdict::Correct)
+                .map(|check| format!("{}: {check}", backend.backend))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert!(mismatches.is_empty(), "\n{}", mismatches.join("\n"));
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
@@ -0,0 +1,18 @@
+use std::{fs, path::Path};
+
+use aoc_core::{KnownAnswers, Verdict};
+
+#[test]
+fn every_known_answer_is_still_found() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let known = KnownAnswers::parse(&fs::read_to_string(root.join("answers.toml")).unwrap())
+        .unwrap_or_else(|error| panic!("answers.toml:{error}"));
+
+    let mismatches: Vec<_> = known
+        .check(&aoc::registry(), root, cfg!(feature = "heavy_test"))
+        .iter()
+        .filter(|check| check.verdict != Verdict::Correct)
+        .map(ToString::to_string)
+        .collect();
+    assert!(mismatches.is_empty(), "\n{}", mismatches.join("\n"));
+}
@@ -1,5 +1,5 @@
 {
-  description = "rust-crane-template";
+  description = "Advent of Code 2023";
 
   inputs = {
     nixpkgs.url = "github:NixOS/nixpkgs/nixpkgs-unstable";
@@ -77,7 +77,7 @@
         # MY_CUSTOM_DEVELOPMENT_VAR = "something else";
 
         # Extra inputs can be added here; cargo and rustc are provided by default.
-        packages = [];
+        packages = [pkgs.deno];
       };
     });
 }